@@ -0,0 +1,158 @@
+use crate::ecdsa::{scalar, PublicKey, RecoverableSignature};
+use crate::field::FieldElement;
+use crate::schnorr::{challenge, SchnorrSignature, XOnlyPublicKey};
+use crate::secp256k1::{S256Point, G, N, P};
+use primitive_types::U256;
+use rand::Rng;
+
+// 各式に 128 ビットの乱数 a_i を掛けて足し合わせ、1 回のマルチスカラー倍算で確かめる。
+// 1 つでも不正な署名があれば、和が O になる確率は 2^-128 程度
+fn coefficients(count: usize) -> Vec<FieldElement<U256>> {
+    let mut rng = rand::thread_rng();
+    (0..count)
+        .map(|i| {
+            // 1 つ目の係数は 1 で十分
+            if i == 0 {
+                scalar(U256::one())
+            } else {
+                scalar(U256::from(rng.gen_range(1..=u128::MAX)))
+            }
+        })
+        .collect()
+}
+
+fn is_infinity(terms: &[(U256, S256Point)]) -> bool {
+    S256Point::multi_mul(terms) == S256Point::infinity()
+}
+
+// BIP340 のバッチ検証:
+// (a_1 s_1 + ... + a_u s_u) G - a_1 R_1 - ... - a_u R_u - a_1 e_1 P_1 - ... - a_u e_u P_u = O
+pub fn schnorr_batch_verify(items: &[(&[u8], XOnlyPublicKey, SchnorrSignature)]) -> bool {
+    let a = coefficients(items.len());
+    let mut s_sum = scalar(U256::zero());
+    let mut terms = Vec::with_capacity(items.len() * 2 + 1);
+
+    for ((message, public_key, sig), a) in items.iter().zip(a) {
+        let (point, r) = match (S256Point::lift_x(public_key.0), S256Point::lift_x(sig.r)) {
+            (Ok(point), Ok(r)) => (point, r),
+            _ => return false,
+        };
+        if sig.r >= P || sig.s >= N {
+            return false;
+        }
+        let e = scalar(challenge(sig.r, public_key.0, message));
+        s_sum = s_sum + a * scalar(sig.s);
+        terms.push(((-a).num, r));
+        terms.push(((-(a * e)).num, point));
+    }
+    terms.push((s_sum.num, G));
+    is_infinity(&terms)
+}
+
+// ECDSA は r = R.x mod n しか持たないので、R を復元できる recoverable な署名を受け取る。
+// s_i R_i = z_i G + r_i P_i を a_i/s_i 倍して足し合わせる:
+// (Σ a_i z_i/s_i) G + Σ (a_i r_i/s_i) P_i - Σ a_i R_i = O
+pub fn ecdsa_batch_verify(items: &[(U256, PublicKey, RecoverableSignature)]) -> bool {
+    let a = coefficients(items.len());
+    let mut u_sum = scalar(U256::zero());
+    let mut terms = Vec::with_capacity(items.len() * 2 + 1);
+
+    for ((z, public_key, sig), a) in items.iter().zip(a) {
+        let r_point = match sig.r_point() {
+            Ok(r_point) => r_point,
+            Err(_) => return false,
+        };
+        let s = scalar(sig.s);
+        u_sum = u_sum + a * scalar(*z) / s;
+        terms.push(((a * scalar(sig.r) / s).num, public_key.point()));
+        terms.push(((-a).num, r_point));
+    }
+    terms.push((u_sum.num, G));
+    is_infinity(&terms)
+}
+
+#[cfg(test)]
+mod batch_tests {
+    use super::*;
+    use crate::ecdsa::{make_hash, PrivateKey};
+    use crate::schnorr::schnorr_sign;
+
+    const MESSAGES: [&[u8]; 4] = [b"", b"a", b"Satoshi Nakamoto", b"Alan Turing"];
+
+    fn schnorr_items() -> Vec<(&'static [u8], XOnlyPublicKey, SchnorrSignature)> {
+        (1..=8u64)
+            .map(|i| {
+                let secret = U256::from(i * 0x1234567);
+                let message = MESSAGES[i as usize % MESSAGES.len()];
+                (
+                    message,
+                    XOnlyPublicKey::from_secret(secret).unwrap(),
+                    schnorr_sign(secret, message, &[i as u8; 32]).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn ecdsa_items() -> Vec<(U256, PublicKey, RecoverableSignature)> {
+        (1..=8u64)
+            .map(|i| {
+                let key = PrivateKey::new(U256::from(i * 0x7654321));
+                let z = make_hash(MESSAGES[i as usize % MESSAGES.len()]);
+                (z, key.public_key.clone(), key.sign_recoverable(z))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn schnorr() {
+        let items = schnorr_items();
+        assert!(schnorr_batch_verify(&items));
+        assert!(schnorr_batch_verify(&items[..1]));
+        assert!(schnorr_batch_verify(&[]));
+
+        // どれか 1 つでも壊れていれば全体が不正
+        let mut bad = items.clone();
+        bad[5].0 = b"forged";
+        assert!(!schnorr_batch_verify(&bad));
+
+        let mut bad = items.clone();
+        bad[2].2.s += U256::one();
+        assert!(!schnorr_batch_verify(&bad));
+
+        let mut bad = items.clone();
+        bad[7].1 = bad[0].1;
+        assert!(!schnorr_batch_verify(&bad));
+
+        let mut bad = items;
+        bad[3].2.r = P;
+        assert!(!schnorr_batch_verify(&bad));
+    }
+
+    #[test]
+    fn ecdsa() {
+        let items = ecdsa_items();
+        for (z, key, sig) in &items {
+            assert!(key.verify(*z, &sig.signature()));
+        }
+        assert!(ecdsa_batch_verify(&items));
+        assert!(ecdsa_batch_verify(&items[..1]));
+        assert!(ecdsa_batch_verify(&[]));
+
+        let mut bad = items.clone();
+        bad[4].0 += U256::one();
+        assert!(!ecdsa_batch_verify(&bad));
+
+        let mut bad = items.clone();
+        bad[1].1 = bad[6].1.clone();
+        assert!(!ecdsa_batch_verify(&bad));
+
+        // R の偶奇を間違えた署名は通らない
+        let mut bad = items.clone();
+        bad[0].2.recovery_id ^= 1;
+        assert!(!ecdsa_batch_verify(&bad));
+
+        let mut bad = items;
+        bad[3].2.s = N;
+        assert!(!ecdsa_batch_verify(&bad));
+    }
+}
@@ -0,0 +1,1068 @@
+// T には 0 や 1 を作るトレイトがないので、x - x や v / v で作っている
+#![allow(clippy::eq_op)]
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use crate::error::CryptoError;
+use crate::field::{Bits, ConditionalSwap, FieldElement, InField};
+
+// 曲線 y^2 = x^3 + a*x + b のパラメータ (p: 体の位数, n: G の位数, h: 余因子)。
+// 点は座標しか持たず、どの曲線の点かは型で区別するので、
+// 違う曲線の点どうしの加算はコンパイルエラーになる
+pub trait Curve: Clone + Copy + fmt::Debug + PartialEq {
+    type Field: PartialEq
+        + Add<Output = Self::Field>
+        + Sub<Output = Self::Field>
+        + Mul<Output = Self::Field>
+        + Div<Output = Self::Field>
+        + ConditionalSwap
+        + InField<Self::Int>
+        + Copy
+        + fmt::Debug;
+    type Int;
+
+    const A: Self::Field;
+    const B: Self::Field;
+    // 体の 1。Field の型からは作れないので、射影座標の Z などのために曲線ごとに与える
+    const ONE: Self::Field;
+    const P: Self::Int;
+    const N: Self::Int;
+    const H: Self::Int;
+    const G: Point<Self>;
+}
+
+// y^2 = x^3 + 7 over F_223。点は無限遠点を含めて 252 個で、G = (47, 71) の位数は 21
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Toy223;
+
+impl Curve for Toy223 {
+    type Field = FieldElement<u64>;
+    type Int = u64;
+
+    const A: Self::Field = FieldElement {
+        num: 0,
+        prime: 223,
+        reduction: PhantomData,
+    };
+    const B: Self::Field = FieldElement {
+        num: 7,
+        prime: 223,
+        reduction: PhantomData,
+    };
+    const ONE: Self::Field = FieldElement {
+        num: 1,
+        prime: 223,
+        reduction: PhantomData,
+    };
+    const P: u64 = 223;
+    const N: u64 = 21;
+    const H: u64 = 12;
+    const G: Point<Self> = Point::Coordinate {
+        x: FieldElement {
+            num: 47,
+            prime: 223,
+            reduction: PhantomData,
+        },
+        y: FieldElement {
+            num: 71,
+            prime: 223,
+            reduction: PhantomData,
+        },
+    };
+}
+
+// Elliptic Curve: y^2 = x^3 + a*x + b
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Point<C: Curve> {
+    Coordinate { x: C::Field, y: C::Field },
+    Infinity,
+}
+
+impl<C: Curve> fmt::Display for Point<C>
+where
+    C::Field: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Point::Coordinate { x, y } => {
+                write!(f, "Point({}, {})", x, y)
+            }
+            Point::Infinity => {
+                write!(f, "Point(infinity)")
+            }
+        }
+    }
+}
+
+impl<C: Curve> Point<C> {
+    pub fn new(x: C::Field, y: C::Field) -> Self {
+        if y * y != x * x * x + C::A * x + C::B {
+            panic!("This is invalid number.");
+        }
+        Self::Coordinate { x, y }
+    }
+
+    // 座標が別の p の体だと曲線の式の計算が panic するので、先に p を確かめる
+    pub fn try_new(x: C::Field, y: C::Field) -> Result<Self, CryptoError> {
+        if !x.in_field(C::P) || !y.in_field(C::P) {
+            return Err(CryptoError::FieldMismatch);
+        }
+        if y * y != x * x * x + C::A * x + C::B {
+            return Err(CryptoError::NotOnCurve);
+        }
+        Ok(Self::Coordinate { x, y })
+    }
+
+    // 違う曲線の点は型で弾けるが、Point::Coordinate を直接書けば C の上にない点も作れる。
+    // 演算子版はそうした点で panic したり意味のない値を返したりするので、外から来た点にはこちらを使う
+    pub fn checked_add(self, other: Self) -> Result<Self, CryptoError> {
+        for point in [self, other] {
+            if let Point::Coordinate { x, y } = point {
+                Self::try_new(x, y).map_err(|_| CryptoError::CurveMismatch)?;
+            }
+        }
+        Ok(self + other)
+    }
+}
+
+#[cfg(test)]
+mod curve_point_tests {
+    use super::*;
+
+    fn f(num: u64) -> FieldElement<u64> {
+        FieldElement::new(num, 223)
+    }
+
+    #[test]
+    fn new() {
+        let _ = Point::<Toy223>::new(f(47), f(71));
+    }
+
+    #[test]
+    fn eq() {
+        let a = Point::<Toy223>::new(f(47), f(71));
+        let b = Point::<Toy223>::new(f(47), f(71));
+
+        assert!(a == b);
+        assert_eq!(a, Toy223::G);
+    }
+
+    #[test]
+    fn try_new() {
+        use crate::error::CryptoError;
+
+        assert_eq!(
+            Point::<Toy223>::try_new(f(47), f(71)),
+            Ok(Point::new(f(47), f(71)))
+        );
+        assert_eq!(
+            Point::<Toy223>::try_new(f(47), f(72)),
+            Err(CryptoError::NotOnCurve)
+        );
+
+        // 別の素数の体の座標は、曲線の式を計算する前に弾く
+        let g = |num| FieldElement::new(num, 227);
+        assert_eq!(
+            Point::<Toy223>::try_new(g(47), g(71)),
+            Err(CryptoError::FieldMismatch)
+        );
+        assert_eq!(
+            Point::<Toy223>::try_new(f(47), g(71)),
+            Err(CryptoError::FieldMismatch)
+        );
+    }
+
+    #[test]
+    fn checked_add() {
+        use crate::error::CryptoError;
+
+        let p = Toy223::G;
+        let q = Point::<Toy223>::new(f(36), f(111));
+        assert_eq!(p.checked_add(q), Ok(p + q));
+        assert_eq!(p.checked_add(Point::Infinity), Ok(p));
+
+        // 曲線の上にない点と、別の体の座標を持つ点
+        let off = Point::<Toy223>::Coordinate { x: f(47), y: f(72) };
+        let other = Point::<Toy223>::Coordinate {
+            x: FieldElement::new(47, 227),
+            y: FieldElement::new(71, 227),
+        };
+        assert_eq!(p.checked_add(off), Err(CryptoError::CurveMismatch));
+        assert_eq!(other.checked_add(p), Err(CryptoError::CurveMismatch));
+    }
+}
+
+impl<C: Curve> Add for Point<C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        use Point::*;
+
+        match (self, other) {
+            (Coordinate { x: x0, y: y0 }, Coordinate { x: x1, y: y1 }) => {
+                if x0 == x1 {
+                    // P + (-P) と、y = 0 での接線 (垂直) はどちらも無限遠点
+                    if y0 != y1 || y0 == y0 - y0 {
+                        return Infinity;
+                    }
+                    // a = 0 の曲線もあるので 1 は y0 / y0 から作る
+                    let one = y0 / y0;
+                    let two = one + one;
+                    let three = one + one + one;
+                    let s = (three * x0 * x0 + C::A) / (two * y0);
+                    let x2 = s * s - two * x0;
+                    return Coordinate {
+                        x: x2,
+                        y: s * (x0 - x2) - y0,
+                    };
+                }
+                let s = (y1 - y0) / (x1 - x0);
+
+                let x2 = s * s - x1 - x0;
+                let y2 = s * (x0 - x2) - y0;
+                Coordinate { x: x2, y: y2 }
+            }
+            (p, Infinity) => p,
+            (Infinity, q) => q,
+        }
+    }
+}
+
+// -(x, y) = (x, -y)
+impl<C: Curve> Neg for Point<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        match self {
+            Point::Coordinate { x, y } => Point::Coordinate { x, y: y - y - y },
+            Point::Infinity => Point::Infinity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod curve_tests {
+    use super::*;
+
+    fn f(num: u64) -> FieldElement<u64> {
+        FieldElement::new(num, 223)
+    }
+
+    #[test]
+    fn point_on_elliptic_curve() {
+        let x = f(192);
+        let y = f(105);
+
+        assert_eq!(y * y, x * x * x + Toy223::A * x + Toy223::B);
+    }
+
+    #[test]
+    fn add_points() {
+        let p0 = Point::<Toy223>::new(f(192), f(105));
+        let p1 = Point::<Toy223>::new(f(17), f(56));
+        let p2 = Point::<Toy223>::new(f(170), f(142));
+
+        assert_ne!(p0, p1);
+        assert_eq!(p0 + p1, p2);
+    }
+
+    #[test]
+    fn parameters() {
+        // 点の個数 = n * h
+        assert_eq!(Toy223::N * Toy223::H, 252);
+        assert_eq!(Toy223::G * Toy223::N, Point::Infinity);
+        assert_ne!(Toy223::G * (Toy223::N - 1), Point::Infinity);
+    }
+}
+
+#[cfg(test)]
+mod group_law_tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    type F223 = FieldElement<u64>;
+
+    // y^2 = x^3 + 7 over F_223 の点を全部並べる (無限遠点を含めて 252 個)
+    fn points() -> Vec<Point<Toy223>> {
+        let mut points = vec![Point::Infinity];
+        for x in 0..223 {
+            for y in 0..223 {
+                if (y * y) % 223 == (x * x * x + 7) % 223 {
+                    points.push(Point::new(F223::new(x, 223), F223::new(y, 223)));
+                }
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn neg() {
+        let p = Point::<Toy223>::new(F223::new(47, 223), F223::new(71, 223));
+        let q = Point::<Toy223>::new(F223::new(47, 223), F223::new(152, 223));
+
+        assert_eq!(-p, q);
+        assert_eq!(-q, p);
+        assert_eq!(-Point::<Toy223>::Infinity, Point::Infinity);
+        assert_eq!(p + q, Point::Infinity);
+    }
+
+    #[test]
+    fn vertical_tangent() {
+        // y = 0 の点は位数 2
+        let p = Point::<Toy223>::new(F223::new(6, 223), F223::new(0, 223));
+
+        assert_eq!(p + p, Point::Infinity);
+        assert_eq!(-p, p);
+        assert_eq!(p * 2u64, Point::Infinity);
+        assert_eq!(p * 3u64, p);
+    }
+
+    #[test]
+    fn count() {
+        assert_eq!(points().len(), 252);
+    }
+
+    proptest! {
+        #[test]
+        fn identity(i in 0..252usize) {
+            let p = points()[i];
+            prop_assert_eq!(p + Point::Infinity, p);
+            prop_assert_eq!(Point::Infinity + p, p);
+        }
+
+        #[test]
+        fn inverse(i in 0..252usize) {
+            let p = points()[i];
+            prop_assert_eq!(p + -p, Point::Infinity);
+            prop_assert_eq!(-p + p, Point::Infinity);
+        }
+
+        #[test]
+        fn commutativity(i in 0..252usize, j in 0..252usize) {
+            let points = points();
+            let (p, q) = (points[i], points[j]);
+            prop_assert_eq!(p + q, q + p);
+        }
+
+        #[test]
+        fn associativity(i in 0..252usize, j in 0..252usize, k in 0..252usize) {
+            let points = points();
+            let (p, q, r) = (points[i], points[j], points[k]);
+            prop_assert_eq!((p + q) + r, p + (q + r));
+        }
+
+        #[test]
+        fn scalar_distributivity(i in 0..252usize, m in 0..300u64, n in 0..300u64) {
+            let p = points()[i];
+            prop_assert_eq!(p * (m + n), p * m + p * n);
+        }
+    }
+}
+// ヤコビアン座標 (X, Y, Z): x = X / Z^2, y = Y / Z^3。
+// 加算・2倍算で割り算を使わず、最後にアフィン座標へ戻すときだけ逆元を取る
+#[derive(Clone, Copy, Debug)]
+enum Jacobian<C: Curve> {
+    Coordinate {
+        x: C::Field,
+        y: C::Field,
+        z: C::Field,
+    },
+    Infinity,
+}
+
+impl<C: Curve> Jacobian<C> {
+    fn from_affine(point: Point<C>) -> Self {
+        match point {
+            Point::Coordinate { x, y } => Jacobian::Coordinate { x, y, z: C::ONE },
+            Point::Infinity => Jacobian::Infinity,
+        }
+    }
+
+    fn to_affine(self) -> Point<C> {
+        match self {
+            Jacobian::Coordinate { x, y, z } => {
+                let z_inv = C::ONE / z;
+                let z_inv2 = z_inv * z_inv;
+                Point::Coordinate {
+                    x: x * z_inv2,
+                    y: y * z_inv2 * z_inv,
+                }
+            }
+            Jacobian::Infinity => Point::Infinity,
+        }
+    }
+
+    // dbl-2007-bl
+    fn double(self) -> Self {
+        match self {
+            Jacobian::Coordinate { x, y, z } => {
+                if y == y - y {
+                    return Jacobian::Infinity;
+                }
+                let xx = x * x;
+                let yy = y * y;
+                let yyyy = yy * yy;
+                let zz = z * z;
+                let s = x * yy;
+                let s = s + s;
+                let s = s + s;
+                let m = xx + xx + xx + C::A * zz * zz;
+                let x3 = m * m - s - s;
+                let yyyy8 = yyyy + yyyy;
+                let yyyy8 = yyyy8 + yyyy8;
+                let yyyy8 = yyyy8 + yyyy8;
+                let yz = y * z;
+                Jacobian::Coordinate {
+                    x: x3,
+                    y: m * (s - x3) - yyyy8,
+                    z: yz + yz,
+                }
+            }
+            Jacobian::Infinity => Jacobian::Infinity,
+        }
+    }
+
+    // add-2007-bl (Z1 = Z2 = 1 を仮定しない一般形)
+    fn add(self, other: Self) -> Self {
+        match (self, other) {
+            (
+                Jacobian::Coordinate {
+                    x: x1,
+                    y: y1,
+                    z: z1,
+                },
+                Jacobian::Coordinate {
+                    x: x2,
+                    y: y2,
+                    z: z2,
+                },
+            ) => {
+                let z1z1 = z1 * z1;
+                let z2z2 = z2 * z2;
+                let u1 = x1 * z2z2;
+                let u2 = x2 * z1z1;
+                let s1 = y1 * z2 * z2z2;
+                let s2 = y2 * z1 * z1z1;
+                if u1 == u2 {
+                    if s1 != s2 {
+                        return Jacobian::Infinity;
+                    }
+                    return self.double();
+                }
+                let h = u2 - u1;
+                let r = s2 - s1;
+                let hh = h * h;
+                let hhh = h * hh;
+                let v = u1 * hh;
+                let x3 = r * r - hhh - v - v;
+                Jacobian::Coordinate {
+                    x: x3,
+                    y: r * (v - x3) - s1 * hhh,
+                    z: z1 * z2 * h,
+                }
+            }
+            (p, Jacobian::Infinity) => p,
+            (Jacobian::Infinity, q) => q,
+        }
+    }
+}
+
+#[cfg(test)]
+mod jacobian_tests {
+    use super::*;
+
+    #[test]
+    fn add_double() {
+        let point = |x: u64, y: u64| {
+            Point::<Toy223>::new(FieldElement::new(x, 223), FieldElement::new(y, 223))
+        };
+        let p0 = point(192, 105);
+        let p1 = point(17, 56);
+        let j0 = Jacobian::from_affine(p0);
+        let j1 = Jacobian::from_affine(p1);
+
+        assert_eq!(j0.add(j1).to_affine(), p0 + p1);
+        assert_eq!(j0.double().to_affine(), p0 + p0);
+        assert_eq!(j0.double().add(j1).to_affine(), p0 + p0 + p1);
+        assert_eq!(j0.add(Jacobian::Infinity).to_affine(), p0);
+        assert_eq!(Jacobian::from_affine(p0).to_affine(), p0);
+    }
+}
+
+// 射影座標 (X : Y : Z): x = X / Z, y = Y / Z、無限遠点は (0 : 1 : 0)。
+// Renes-Costello-Batina の完全な加算公式を使うので、無限遠点どうしや P = Q、P = -Q でも
+// 場合分けがない。位数 2 の点がない (位数が奇数の) 部分群の中で使う
+#[derive(Clone, Copy, Debug)]
+struct Projective<C: Curve> {
+    x: C::Field,
+    y: C::Field,
+    z: C::Field,
+}
+
+impl<C: Curve> Projective<C> {
+    fn identity() -> Self {
+        let zero = C::ONE - C::ONE;
+        Self {
+            x: zero,
+            y: C::ONE,
+            z: zero,
+        }
+    }
+
+    fn from_affine(point: Point<C>) -> Self {
+        match point {
+            Point::Coordinate { x, y } => Self { x, y, z: C::ONE },
+            Point::Infinity => Self::identity(),
+        }
+    }
+
+    fn to_affine(self) -> Point<C> {
+        let zero = self.z - self.z;
+        if self.z == zero {
+            return Point::Infinity;
+        }
+        let z_inv = C::ONE / self.z;
+        Point::Coordinate {
+            x: self.x * z_inv,
+            y: self.y * z_inv,
+        }
+    }
+
+    // Renes-Costello-Batina 2016, Algorithm 1 (任意の a に使える一般形)
+    fn add(self, other: Self) -> Self {
+        let (x1, y1, z1) = (self.x, self.y, self.z);
+        let (x2, y2, z2) = (other.x, other.y, other.z);
+        let a = C::A;
+        let b3 = C::B + C::B + C::B;
+
+        let t0 = x1 * x2;
+        let t1 = y1 * y2;
+        let t2 = z1 * z2;
+        let t3 = (x1 + y1) * (x2 + y2) - (t0 + t1);
+        let t4 = (x1 + z1) * (x2 + z2) - (t0 + t2);
+        let t5 = (y1 + z1) * (y2 + z2) - (t1 + t2);
+        let z3 = a * t4 + b3 * t2;
+        let x3 = t1 - z3;
+        let z3 = t1 + z3;
+        let y3 = x3 * z3;
+        let t1 = t0 + t0 + t0 + a * t2;
+        let t2 = a * (t0 - a * t2);
+        let t4 = b3 * t4 + t2;
+        Self {
+            x: t3 * x3 - t5 * t4,
+            y: y3 + t1 * t4,
+            z: t5 * z3 + t3 * t1,
+        }
+    }
+}
+
+impl<C: Curve> ConditionalSwap for Projective<C> {
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: bool) {
+        C::Field::conditional_swap(&mut a.x, &mut b.x, choice);
+        C::Field::conditional_swap(&mut a.y, &mut b.y, choice);
+        C::Field::conditional_swap(&mut a.z, &mut b.z, choice);
+    }
+}
+
+#[cfg(test)]
+mod projective_tests {
+    use super::*;
+
+    fn point(x: u64, y: u64) -> Point<Toy223> {
+        Point::new(FieldElement::new(x, 223), FieldElement::new(y, 223))
+    }
+
+    #[test]
+    fn add() {
+        let p0 = point(192, 105);
+        let p1 = point(17, 56);
+        let q0 = Projective::from_affine(p0);
+        let q1 = Projective::from_affine(p1);
+        let o = Projective::<Toy223>::identity();
+
+        assert_eq!(q0.add(q1).to_affine(), p0 + p1);
+        assert_eq!(q0.add(q0).to_affine(), p0 + p0);
+        assert_eq!(q0.add(q0).add(q1).to_affine(), p0 + p0 + p1);
+        // 無限遠点が絡む場合も同じ式で計算できる
+        assert_eq!(q0.add(o).to_affine(), p0);
+        assert_eq!(o.add(q0).to_affine(), p0);
+        assert_eq!(o.add(o).to_affine(), Point::Infinity);
+        assert_eq!(
+            q0.add(Projective::from_affine(-p0)).to_affine(),
+            Point::Infinity
+        );
+        assert_eq!(Projective::from_affine(p0).to_affine(), p0);
+    }
+}
+
+impl<C, U> Mul<U> for Point<C>
+where
+    C: Curve,
+    U: Add<Output = U>
+        + Sub<Output = U>
+        + Mul<Output = U>
+        + Div<Output = U>
+        + Rem<Output = U>
+        + From<C::Int>
+        + PartialOrd
+        + Copy,
+{
+    type Output = Point<C>;
+
+    // double-and-add: スカラーの下位ビットから順に見ていく。途中はヤコビアン座標で計算する。
+    // どの点の位数も群の位数 n*h を割り切るので、スカラーは先に n*h で簡約しておく
+    fn mul(self, other: U) -> Self::Output {
+        let mut coef = other % (U::from(C::N) * U::from(C::H));
+        let zero = coef - coef;
+        if coef == zero {
+            return Self::Infinity;
+        }
+        let one = coef / coef;
+        let two = one + one;
+        let mut current = Jacobian::from_affine(self);
+        let mut ret = Jacobian::Infinity;
+        while coef > zero {
+            if coef % two == one {
+                ret = ret.add(current);
+            }
+            current = current.double();
+            coef = coef / two;
+        }
+        ret.to_affine()
+    }
+}
+
+#[cfg(test)]
+mod curve_mul_tests {
+    use super::*;
+    use primitive_types::U256;
+
+    fn point(x: u64, y: u64) -> Point<Toy223> {
+        Point::new(FieldElement::new(x, 223), FieldElement::new(y, 223))
+    }
+
+    #[test]
+    fn mul() {
+        let p0 = point(47, 71);
+        let p1 = point(36, 111);
+
+        assert_ne!(p0, p1);
+        assert_eq!(p0 * 2u64, p1);
+        assert_eq!(p0 * U256::from(2), p1);
+        assert_eq!(p0 * U256::zero(), Point::Infinity);
+    }
+
+    #[test]
+    fn mul_f223() {
+        let p = point(15, 86);
+
+        // (15, 86) は位数 7 の点
+        assert_eq!(p * U256::from(7), Point::Infinity);
+        assert_eq!(p * U256::from(8), p);
+
+        let p2 = point(47, 71);
+
+        assert_eq!(p2 * U256::from(21), Point::Infinity);
+        assert_eq!(p2 * U256::from(20), -p2);
+        assert_eq!(p2 * U256::from(4), point(194, 51));
+    }
+
+    #[test]
+    fn mul_reduced_scalar() {
+        // 群の位数は n*h = 252 で、G の部分群 (位数 21) に入らない点もある。
+        // n で簡約すると p * 21 が無限遠点になってしまうので、加算を繰り返した値と比べる
+        let order = Toy223::N * Toy223::H;
+        let points: Vec<Point<Toy223>> = (0..223)
+            .flat_map(|x| (0..223).map(move |y| (x, y)))
+            .filter_map(|(x, y)| {
+                Point::try_new(FieldElement::new(x, 223), FieldElement::new(y, 223)).ok()
+            })
+            .collect();
+        assert_eq!(points.len(), 251);
+        for p in points {
+            let sum = (0..Toy223::N).fold(Point::Infinity, |acc, _| acc + p);
+            assert_eq!(p * Toy223::N, sum);
+            assert_eq!(p * (order + Toy223::N), sum);
+            assert_eq!(p * U256::from(order + Toy223::N), sum);
+            assert_eq!(p * order, Point::Infinity);
+        }
+    }
+}
+
+impl<C: Curve> Point<C> {
+    // Montgomery ladder: 秘密のスカラー用。上位 bits ビットを必ず全部処理し、
+    // ビットの値では分岐せず条件付き入れ替えで R0 = kP, R1 = (k+1)P を保つ
+    pub fn mul_ladder<U: Bits>(self, scalar: U, bits: usize) -> Self {
+        Self::ladder(
+            Projective::identity(),
+            Projective::from_affine(self),
+            (0..bits).rev().map(|i| scalar.bit(i)),
+        )
+    }
+
+    // 上位のビットから順に (R0, R1) = (2R0, R0 + R1) か (R0 + R1, 2R1) に進める。
+    // 完全な加算公式なので、途中で R0 や R1 が無限遠点になっても計算の手順は変わらない
+    fn ladder(
+        mut r0: Projective<C>,
+        mut r1: Projective<C>,
+        bits: impl Iterator<Item = bool>,
+    ) -> Self {
+        for b in bits {
+            Projective::conditional_swap(&mut r0, &mut r1, b);
+            r1 = r0.add(r1);
+            r0 = r0.add(r0);
+            Projective::conditional_swap(&mut r0, &mut r1, b);
+        }
+        r0.to_affine()
+    }
+
+    // k1*P1 + k2*P2 + ... をまとめて計算する。
+    // 項が少ないうちは Straus、多くなったら Pippenger のバケット法を使う
+    pub fn multi_mul<U: Bits>(terms: &[(U, Point<C>)]) -> Self {
+        if terms.len() <= STRAUS_MAX_TERMS {
+            Self::straus(terms)
+        } else {
+            Self::pippenger(terms)
+        }
+    }
+
+    // 各点について 1P..15P を用意し、4 ビットずつ 2倍算を全項で共有する
+    fn straus<U: Bits>(terms: &[(U, Point<C>)]) -> Self {
+        let bits = terms.iter().map(|(k, _)| k.bits()).max().unwrap_or(0);
+        let tables: Vec<Vec<Jacobian<C>>> = terms
+            .iter()
+            .map(|(_, p)| {
+                let p = Jacobian::from_affine(*p);
+                let mut table = vec![p];
+                for _ in 1..(1 << STRAUS_WINDOW) - 1 {
+                    table.push(table[table.len() - 1].add(p));
+                }
+                table
+            })
+            .collect();
+
+        let mut ret = Jacobian::Infinity;
+        for window in (0..bits.div_ceil(STRAUS_WINDOW)).rev() {
+            for _ in 0..STRAUS_WINDOW {
+                ret = ret.double();
+            }
+            for ((k, _), table) in terms.iter().zip(&tables) {
+                let digit = window_digit(k, window, STRAUS_WINDOW);
+                if digit != 0 {
+                    ret = ret.add(table[digit - 1]);
+                }
+            }
+        }
+        ret.to_affine()
+    }
+
+    // c ビットの窓ごとに、桁の値が同じ点をバケットに集めてから
+    // Σ d * bucket[d] を累積和 2 回で求める
+    fn pippenger<U: Bits>(terms: &[(U, Point<C>)]) -> Self {
+        let bits = terms.iter().map(|(k, _)| k.bits()).max().unwrap_or(0);
+        let c = pippenger_window(terms.len());
+        let points: Vec<_> = terms
+            .iter()
+            .map(|(_, p)| Jacobian::from_affine(*p))
+            .collect();
+
+        let mut ret = Jacobian::Infinity;
+        for window in (0..bits.div_ceil(c)).rev() {
+            for _ in 0..c {
+                ret = ret.double();
+            }
+            let mut buckets = vec![Jacobian::Infinity; (1 << c) - 1];
+            for ((k, _), p) in terms.iter().zip(&points) {
+                let digit = window_digit(k, window, c);
+                if digit != 0 {
+                    buckets[digit - 1] = buckets[digit - 1].add(*p);
+                }
+            }
+            let mut running = Jacobian::Infinity;
+            let mut sum = Jacobian::Infinity;
+            for bucket in buckets.into_iter().rev() {
+                running = running.add(bucket);
+                sum = sum.add(running);
+            }
+            ret = ret.add(sum);
+        }
+        ret.to_affine()
+    }
+}
+
+impl<C: Curve> Point<C>
+where
+    C::Int: Bits,
+{
+    // 秘密鍵やノンス k (0 < k < n) 用のスカラー倍。
+    // k + n か k + 2n のうち n のビット長 + 1 ビットちょうどになる方を使うと最上位ビットが必ず 1 になり、
+    // k の長さによらずラダーを (P, 2P) から同じ回数だけ回せる
+    pub fn mul_secret(self, secret: C::Int) -> Self {
+        let bits = C::N.bits();
+        let k: Vec<bool> = (0..bits).map(|i| secret.bit(i)).collect();
+        let k1 = add_bits(&k, &C::N, bits);
+        let k2 = add_bits(&k1, &C::N, bits);
+        let short = !k1[bits];
+        let scalar: Vec<bool> = k1
+            .iter()
+            .zip(&k2)
+            .map(|(a, b)| (a & !short) | (b & short))
+            .collect();
+
+        let p = Projective::from_affine(self);
+        Self::ladder(p, p.add(p), scalar[..bits].iter().rev().copied())
+    }
+}
+
+// a + b をビット列 (下位から) のまま繰り上がり付きで足す。
+// C::Int の幅ちょうどの位数 (P-256 など) でも k + 2n が溢れない
+fn add_bits<U: Bits>(a: &[bool], b: &U, b_bits: usize) -> Vec<bool> {
+    let mut carry = false;
+    let mut sum: Vec<bool> = a
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            let y = i < b_bits && b.bit(i);
+            let s = x ^ y ^ carry;
+            carry = (x & y) | (carry & (x ^ y));
+            s
+        })
+        .collect();
+    sum.push(carry);
+    sum
+}
+
+const STRAUS_WINDOW: usize = 4;
+const STRAUS_MAX_TERMS: usize = 32;
+
+// 項数 n に対して 2倍算・加算の合計がおおよそ最小になる窓幅 (≒ log2(n) - 2)
+fn pippenger_window(n: usize) -> usize {
+    let log2 = (usize::BITS - n.leading_zeros()) as usize;
+    log2.saturating_sub(2).max(4)
+}
+
+// k の window 番目の width ビットの値
+fn window_digit<U: Bits>(k: &U, window: usize, width: usize) -> usize {
+    (0..width).fold(0, |digit, j| {
+        digit | ((k.bit(window * width + j) as usize) << j)
+    })
+}
+
+const FIXED_BASE_WINDOW: usize = 4;
+
+// 固定された基点 B の倍数表。スカラー k を 4 ビットずつの桁 d_i に分け、
+// 行 i から (d_i * 16^i) B を取り出して足すだけで kB が求まる (2倍算が要らない)。
+// 表も和も射影座標の完全な加算公式で扱うので、桁が 0 の行 (無限遠点) や同じ点どうしの和でも
+// 計算の手順は変わらない。そのため B は位数が奇数の点に限る
+pub struct FixedBaseTable<C: Curve> {
+    rows: Vec<[Projective<C>; 1 << FIXED_BASE_WINDOW]>,
+}
+
+impl<C: Curve> FixedBaseTable<C> {
+    // bits ビットまでのスカラーに使える表を作る
+    pub fn new(base: Point<C>, bits: usize) -> Self {
+        let windows = bits.div_ceil(FIXED_BASE_WINDOW);
+        let mut rows = Vec::with_capacity(windows);
+        let mut power = Projective::from_affine(base);
+        for _ in 0..windows {
+            let mut row = [Projective::identity(); 1 << FIXED_BASE_WINDOW];
+            for d in 1..row.len() {
+                row[d] = row[d - 1].add(power);
+            }
+            rows.push(row);
+            for _ in 0..FIXED_BASE_WINDOW {
+                power = power.add(power);
+            }
+        }
+        Self { rows }
+    }
+
+    // 行ごとに全要素をなめて条件付き入れ替えで桁の要素を選ぶので、
+    // 表のどこを読んだかからスカラーは漏れない。bits を超える上位ビットは無視する
+    pub fn mul<U: Bits>(&self, scalar: U) -> Point<C> {
+        let mut ret = Projective::identity();
+        for (i, row) in self.rows.iter().enumerate() {
+            let digit = window_digit(&scalar, i, FIXED_BASE_WINDOW);
+            let mut entry = row[0];
+            for (d, candidate) in row.iter().enumerate().skip(1) {
+                let mut candidate = *candidate;
+                Projective::conditional_swap(&mut entry, &mut candidate, d == digit);
+            }
+            ret = ret.add(entry);
+        }
+        ret.to_affine()
+    }
+}
+
+#[cfg(test)]
+mod curve_ladder_tests {
+    use super::*;
+    use primitive_types::U256;
+
+    fn point(x: u64, y: u64) -> Point<Toy223> {
+        Point::new(FieldElement::new(x, 223), FieldElement::new(y, 223))
+    }
+
+    #[test]
+    fn conditional_swap() {
+        let mut a = U256::from(1);
+        let mut b = U256::MAX;
+        U256::conditional_swap(&mut a, &mut b, false);
+        assert_eq!((a, b), (U256::from(1), U256::MAX));
+        U256::conditional_swap(&mut a, &mut b, true);
+        assert_eq!((a, b), (U256::MAX, U256::from(1)));
+    }
+
+    #[test]
+    fn mul_ladder() {
+        let p = point(47, 71);
+
+        for k in 1..20u64 {
+            assert_eq!(p.mul_ladder(U256::from(k), 8), p * U256::from(k));
+        }
+    }
+
+    fn check_mul_secret<C: Curve<Int = T>, T: Bits + Sub<Output = T> + Copy>(one: T, two: T)
+    where
+        Point<C>: Mul<T, Output = Point<C>>,
+    {
+        let n = C::N;
+        for k in [one, two, n - two, n - one] {
+            assert_eq!(C::G.mul_secret(k), C::G * k);
+        }
+        assert_eq!(C::G.mul_secret(one), C::G);
+        assert_eq!(C::G.mul_secret(n - one), -C::G);
+    }
+
+    // k = 1 は k + 2n = 2n + 1 を使うので途中で R0 = nP、k = n - 1 は k + n = 2n - 1 なので R1 = nP になる。
+    // ヤコビアン座標なら無限遠点の場合分けに入るところだが、射影座標の完全な公式ではそのまま計算が進む
+    #[test]
+    fn mul_secret_edges() {
+        use crate::field::U576;
+        use crate::secp256k1::Secp256k1;
+        use crate::secp256r1::Secp256r1;
+        use crate::secp384r1::Secp384r1;
+        use crate::secp521r1::Secp521r1;
+        use primitive_types::U512;
+
+        check_mul_secret::<Secp256k1, _>(U256::one(), U256::from(2));
+        check_mul_secret::<Secp256r1, _>(U256::one(), U256::from(2));
+        check_mul_secret::<Secp384r1, _>(U512::one(), U512::from(2));
+        check_mul_secret::<Secp521r1, _>(U576::one(), U576::from(2));
+    }
+
+    // C::ONE は体の 1 で、割り算で作った v / v と一致する
+    fn check_one<C: Curve>() {
+        if let Point::Coordinate { x, y } = C::G {
+            assert_eq!(C::ONE, x / x);
+            assert_eq!(C::ONE * y, y);
+        }
+        assert_eq!(Jacobian::from_affine(C::G).to_affine(), C::G);
+        assert_eq!(Projective::from_affine(C::G).to_affine(), C::G);
+    }
+
+    #[test]
+    fn one() {
+        use crate::secp256k1::Secp256k1;
+        use crate::secp256r1::Secp256r1;
+        use crate::secp384r1::Secp384r1;
+        use crate::secp521r1::Secp521r1;
+
+        check_one::<Toy223>();
+        check_one::<Secp256k1>();
+        check_one::<Secp256r1>();
+        check_one::<Secp384r1>();
+        check_one::<Secp521r1>();
+    }
+
+    #[test]
+    fn add_bits() {
+        let bits = |v: u64, len: usize| (0..len).map(|i| v >> i & 1 == 1).collect::<Vec<_>>();
+        let n = U256::from(0b1011u64);
+        assert_eq!(super::add_bits(&bits(0b0110, 4), &n, 4), bits(0b10001, 5));
+        assert_eq!(super::add_bits(&bits(0b10001, 5), &n, 4), bits(0b011100, 6));
+    }
+
+    #[test]
+    fn multi_mul() {
+        let p = point(47, 71);
+        let q = point(143, 98);
+        let r = point(15, 86);
+
+        let terms = [
+            (U256::from(5), p),
+            (U256::from(123), q),
+            (U256::from(0), r),
+            (U256::from(1000), r),
+        ];
+        let expected = p * U256::from(5) + q * U256::from(123) + r * U256::from(1000);
+        assert_eq!(Point::multi_mul(&terms), expected);
+
+        // 打ち消し合う項と空の入力は無限遠点
+        assert_eq!(
+            Point::multi_mul(&[(U256::from(3), p), (U256::from(3), -p)]),
+            Point::Infinity
+        );
+        assert_eq!(Point::<Toy223>::multi_mul::<U256>(&[]), Point::Infinity);
+    }
+
+    #[test]
+    fn straus_pippenger() {
+        let g = Toy223::G;
+
+        // Straus と Pippenger の切り替えをまたぐ項数で、素朴な和と比べる
+        for n in [1, 2, 5, STRAUS_MAX_TERMS, STRAUS_MAX_TERMS + 1, 100] {
+            let terms: Vec<_> = (0..n as u64)
+                .map(|i| {
+                    let k = U256::from((i * 7919 + 13) * 104729 % 65521);
+                    (k, g * U256::from(i + 1))
+                })
+                .collect();
+            let expected = terms
+                .iter()
+                .fold(Point::Infinity, |acc, (k, p)| acc + *p * *k);
+            assert_eq!(Point::straus(&terms), expected);
+            assert_eq!(Point::pippenger(&terms), expected);
+            assert_eq!(Point::multi_mul(&terms), expected);
+        }
+    }
+
+    #[test]
+    fn fixed_base_table() {
+        // 桁 0 の要素は無限遠点で、位数 7 の点では行の途中にも現れるが、完全な公式なので結果は変わらない
+        for base in [point(47, 71), point(15, 86)] {
+            let table = FixedBaseTable::new(base, 12);
+            for k in 0..300u64 {
+                assert_eq!(table.mul(U256::from(k)), base * U256::from(k));
+            }
+            assert_eq!(table.mul(U256::from(0xfffu64)), base * U256::from(0xfffu64));
+        }
+    }
+
+    #[test]
+    fn window_digit() {
+        let k = U256::from(0xabcdu64);
+        assert_eq!(super::window_digit(&k, 0, 4), 0xd);
+        assert_eq!(super::window_digit(&k, 3, 4), 0xa);
+        assert_eq!(super::window_digit(&k, 1, 8), 0xab);
+        assert_eq!(super::window_digit(&k, 4, 4), 0);
+        assert_eq!(pippenger_window(8), 4);
+        assert_eq!(pippenger_window(1 << 12), 11);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::{Secp256k1, GX, GY, P};
+
+    #[test]
+    fn on_the_curve() {
+        let gx = FieldElement::new(GX, P);
+        let gy = FieldElement::new(GY, P);
+
+        assert_eq!(Point::<Secp256k1>::new(gx, gy), Secp256k1::G);
+    }
+}
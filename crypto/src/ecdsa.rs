@@ -0,0 +1,1227 @@
+use crate::curve::{Curve, Point};
+use crate::error::{CryptoError, DerError};
+use crate::field::{Bits, FieldElement, Fp, GenericReduction, Reduction, UInt};
+use crate::rfc6979::Rfc6979;
+use crate::secp256k1::{to_bytes, S256Point, Secp256k1, G, N};
+use crate::secp256r1::{P256Reduction, Secp256r1};
+use crate::secp384r1::Secp384r1;
+use crate::secp521r1::{P521Reduction, Secp521r1};
+use primitive_types::U256;
+use sha2::digest::core_api::BlockSizeUser;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use std::fmt;
+
+pub fn make_hash(source: &[u8]) -> U256 {
+    let mut hasher = Sha256::new();
+    hasher.update(source);
+    U256::from(&hasher.finalize()[..])
+}
+
+// hash256 = SHA256(SHA256(source))
+pub fn hash256(source: &[u8]) -> U256 {
+    U256::from(&Sha256::digest(Sha256::digest(source))[..])
+}
+
+// 署名ハッシュ z を位数 n の体に載せる
+pub fn scalar(num: U256) -> FieldElement<U256> {
+    FieldElement::new(num % N, N)
+}
+
+// r, s は曲線の位数 n を法とする整数なので、どの曲線の署名かを型で区別する
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Signature<C: Curve = Secp256k1> {
+    pub r: C::Int,
+    pub s: C::Int,
+}
+
+impl<C: Curve> fmt::Display for Signature<C>
+where
+    C::Int: fmt::LowerHex,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Signature({:x}, {:x})", self.r, self.s)
+    }
+}
+
+// low-S は Bitcoin の規則なので secp256k1 の署名にだけ用意する
+impl Signature<Secp256k1> {
+    // s と n - s はどちらも検証を通るので、BIP62/BIP146 に従い s <= n/2 を正規形とする
+    pub fn is_low_s(&self) -> bool {
+        self.s <= N >> 1
+    }
+
+    pub fn normalize_s(&self) -> Self {
+        if self.is_low_s() {
+            *self
+        } else {
+            Signature {
+                r: self.r,
+                s: N - self.s,
+            }
+        }
+    }
+}
+
+// DER の整数は曲線の体の長さ (field_len) までとして読み書きする
+impl<C: EcdsaCurve> Signature<C> {
+    // 0x30 len 0x02 len(r) r 0x02 len(s) s。
+    // P-521 では中身が 128 バイトを超えることがあり、そのときは len を 0x81 len と書く
+    pub fn der(&self) -> Vec<u8> {
+        let len = field_len::<C>();
+        let r = der_integer(&self.r.to_be_bytes(len));
+        let s = der_integer(&self.s.to_be_bytes(len));
+        let body = r.len() + s.len();
+        let mut ret = vec![0x30];
+        if body >= 0x80 {
+            ret.push(0x81);
+        }
+        ret.push(body as u8);
+        ret.extend(r);
+        ret.extend(s);
+        ret
+    }
+
+    // BIP66 と同じ厳格な DER のみ受け付ける。列の長さも最短の形でなければならない
+    pub fn parse_der(der: &[u8]) -> Result<Self, CryptoError> {
+        let err = |e| Err(CryptoError::InvalidDer(e));
+        let len = field_len::<C>();
+        // 整数 1 つは最長で 0x02 len 0x00 と len バイト
+        let max_body = 2 * (len + 3);
+        let max_len = max_body + if max_body >= 0x80 { 3 } else { 2 };
+        if der.len() < 8 || der.len() > max_len {
+            return err(DerError::InvalidLength);
+        }
+        if der[0] != 0x30 {
+            return err(DerError::NotSequence);
+        }
+        let (start, body) = match der[1] {
+            0x81 if der[2] >= 0x80 => (3, der[2] as usize),
+            body if body < 0x80 => (2, body as usize),
+            _ => return err(DerError::SequenceLengthMismatch),
+        };
+        if body != der.len() - start {
+            return err(DerError::SequenceLengthMismatch);
+        }
+        let len_r = der[start + 1] as usize;
+        if start + 3 + len_r >= der.len() {
+            return err(DerError::IntegerLengthMismatch);
+        }
+        let len_s = der[start + 3 + len_r] as usize;
+        if start + len_r + len_s + 4 != der.len() {
+            return err(DerError::IntegerLengthMismatch);
+        }
+        let r = parse_der_integer(&der[start..start + 2 + len_r], len)?;
+        let s = parse_der_integer(&der[start + 2 + len_r..], len)?;
+        Ok(Signature { r, s })
+    }
+}
+
+// bytes は上位を 0 で埋めたビッグエンディアン
+fn der_integer(bytes: &[u8]) -> Vec<u8> {
+    let start = bytes
+        .iter()
+        .position(|b| *b != 0)
+        .unwrap_or(bytes.len() - 1);
+    let value = &bytes[start..];
+    let mut ret = vec![0x02];
+    // 先頭ビットが立っていると負数になるので 0x00 を足す
+    if value[0] & 0x80 != 0 {
+        ret.push(value.len() as u8 + 1);
+        ret.push(0);
+    } else {
+        ret.push(value.len() as u8);
+    }
+    ret.extend_from_slice(value);
+    ret
+}
+
+// 0x02 len value (長さの整合性は呼び出し側で確認済み)。値は len バイトまで
+fn parse_der_integer<T: UInt>(der: &[u8], len: usize) -> Result<T, CryptoError> {
+    let err = |e| Err(CryptoError::InvalidDer(e));
+    if der[0] != 0x02 {
+        return err(DerError::NotInteger);
+    }
+    let value = &der[2..];
+    if value.is_empty() {
+        return err(DerError::ZeroLengthInteger);
+    }
+    if value[0] & 0x80 != 0 {
+        return err(DerError::NegativeInteger);
+    }
+    if value.len() > 1 && value[0] == 0 && value[1] & 0x80 == 0 {
+        return err(DerError::ExcessivePadding);
+    }
+    let value = if value[0] == 0 { &value[1..] } else { value };
+    if value.len() > len {
+        return err(DerError::IntegerTooLarge);
+    }
+    Ok(T::from(value))
+}
+
+// recovery_id の bit 0 は R.y の偶奇、bit 1 は R.x = r + n かどうか
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RecoverableSignature {
+    pub r: U256,
+    pub s: U256,
+    pub recovery_id: u8,
+}
+
+impl fmt::Display for RecoverableSignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "RecoverableSignature({:x}, {:x}, {})",
+            self.r, self.s, self.recovery_id
+        )
+    }
+}
+
+impl RecoverableSignature {
+    pub fn signature(&self) -> Signature {
+        Signature {
+            r: self.r,
+            s: self.s,
+        }
+    }
+
+    // 署名時の R = k*G を r と recovery_id から復元する
+    pub fn r_point(&self) -> Result<S256Point, CryptoError> {
+        if self.r.is_zero()
+            || self.r >= N
+            || self.s.is_zero()
+            || self.s >= N
+            || self.recovery_id > 3
+        {
+            return Err(CryptoError::OutOfRange);
+        }
+        let x = if self.recovery_id & 2 == 0 {
+            self.r
+        } else {
+            // r + n >= p なら R.x にはなりえない
+            self.r.checked_add(N).ok_or(CryptoError::OutOfRange)?
+        };
+        let mut sec = vec![2 + (self.recovery_id & 1)];
+        sec.extend_from_slice(&to_bytes(x));
+        S256Point::parse_sec(&sec)
+    }
+}
+
+// s*R = z*G + r*P より P = (s*R - z*G)/r
+pub fn recover_public_key(z: U256, sig: &RecoverableSignature) -> Result<S256Point, CryptoError> {
+    let point = sig.r_point()?;
+
+    let z = scalar(z);
+    let r = scalar(sig.r);
+    let s = scalar(sig.s);
+    let u = -z / r;
+    let v = s / r;
+    let total = S256Point::multi_mul(&[(u.num, G), (v.num, point)]);
+
+    match total.0 {
+        Point::Coordinate { .. } => Ok(total),
+        Point::Infinity => Err(CryptoError::InvalidSignature),
+    }
+}
+
+// secp256k1 の公開鍵。検証そのものは VerifyingKey<Secp256k1> に任せ、low-S の確認だけを足す
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublicKey {
+    point: S256Point,
+}
+
+impl PublicKey {
+    pub fn new(point: S256Point) -> Result<Self, CryptoError> {
+        VerifyingKey::new(point.0)?;
+        Ok(Self { point })
+    }
+
+    pub fn point(&self) -> S256Point {
+        self.point
+    }
+
+    pub fn verify(&self, z: U256, sig: &Signature) -> bool {
+        VerifyingKey {
+            point: self.point.0,
+        }
+        .verify(z, sig)
+    }
+
+    // high-S の署名を受け付けない検証
+    pub fn verify_strict(&self, z: U256, sig: &Signature) -> bool {
+        sig.is_low_s() && self.verify(z, sig)
+    }
+}
+
+// secp256k1 の秘密鍵。k*G を倍数表で求め、署名を low-S に正規化して recovery_id を付ける
+pub struct PrivateKey {
+    key: SigningKey<Secp256k1>,
+    pub public_key: PublicKey,
+}
+
+impl PrivateKey {
+    pub fn new(secret: U256) -> Self {
+        if secret.is_zero() || secret >= N {
+            panic!("Secret {:?} not in range 1 to {:?}", secret, N)
+        }
+        let point = S256Point::mul_base(secret);
+        Self {
+            key: SigningKey {
+                secret,
+                verifying_key: VerifyingKey { point: point.0 },
+            },
+            public_key: PublicKey { point },
+        }
+    }
+
+    pub fn try_new(secret: U256) -> Result<Self, CryptoError> {
+        if secret.is_zero() || secret >= N {
+            return Err(CryptoError::OutOfRange);
+        }
+        Ok(Self::new(secret))
+    }
+
+    // k は RFC 6979 で秘密鍵と z から決定的に作る
+    pub fn sign(&self, z: U256) -> Signature {
+        self.sign_recoverable(z).signature()
+    }
+
+    pub fn sign_recoverable(&self, z: U256) -> RecoverableSignature {
+        Rfc6979::new(self.key.secret, z, N)
+            .find_map(|k| self.sign_recoverable_with_nonce(z, k))
+            .unwrap()
+    }
+
+    pub fn sign_with_nonce(&self, z: U256, k: U256) -> Option<Signature> {
+        self.sign_recoverable_with_nonce(z, k)
+            .map(|sig| sig.signature())
+    }
+
+    // R = k*G から署名し、recovery_id に R.y の偶奇と R.x >= n かどうかを入れる
+    pub fn sign_recoverable_with_nonce(&self, z: U256, k: U256) -> Option<RecoverableSignature> {
+        let point = S256Point::mul_base(k).0;
+        let sig = self.key.sign_with_point(z, k, point)?;
+        let (x, y) = match point {
+            Point::Coordinate { x, y } => (x.num, y.num),
+            Point::Infinity => return None,
+        };
+        let mut recovery_id = y.bit(0) as u8 | if x >= N { 2 } else { 0 };
+        // s を n - s にすると R が -R に入れ替わり、y の偶奇が反転する
+        if !sig.is_low_s() {
+            recovery_id ^= 1;
+        }
+        let sig = sig.normalize_s();
+        Some(RecoverableSignature {
+            r: sig.r,
+            s: sig.s,
+            recovery_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod ecdsa_tests {
+    use super::*;
+
+    fn hex(s: &str) -> U256 {
+        U256::from_str_radix(s, 16).unwrap()
+    }
+
+    fn point(x: &str, y: &str) -> S256Point {
+        S256Point::new(hex(x), hex(y))
+    }
+
+    #[test]
+    fn verify() {
+        let key = PublicKey::new(point(
+            "887387e452b8eacc4acfde10d9aaf7f6d9a0f975aabb10d006e4da568744d06c",
+            "61de6d95231cd89026e286df3b6ae4a894a3378e393e93a0f45b666329a0ae34",
+        ))
+        .unwrap();
+
+        let z = hex("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60");
+        let sig = Signature {
+            r: hex("ac8d1c87e51d0d441be8b3dd5b05c8795b48875dffe00b7ffcfac23010d3a395"),
+            s: hex("68342ceff8935ededd102dd876ffd6ba72d6a427a3edb13d26eb0781cb423c4"),
+        };
+        assert!(key.verify(z, &sig));
+
+        let z = hex("7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d");
+        let sig = Signature {
+            r: hex("eff69ef2b1bd93a66ed5219add4fb51e11a840f404876325a1e8ffe0529a2c"),
+            s: hex("c7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6"),
+        };
+        assert!(key.verify(z, &sig));
+        assert!(!key.verify(z + U256::one(), &sig));
+    }
+
+    #[test]
+    fn infinity_public_key() {
+        // 公開鍵が O なら R = k*G, s = z/k で u*G + v*O = R となり、検証式だけなら通ってしまう。
+        // O の公開鍵は作ることも読み込むこともできない
+        assert_eq!(
+            PublicKey::new(S256Point::infinity()),
+            Err(CryptoError::InfinityPublicKey)
+        );
+        assert_eq!(
+            S256Point::parse_sec(&[0]),
+            Err(CryptoError::InfinityPublicKey)
+        );
+        assert_eq!(
+            VerifyingKey::<Secp256k1>::new(Point::Infinity),
+            Err(CryptoError::InfinityPublicKey)
+        );
+        assert_eq!(
+            VerifyingKey::<Secp256k1>::parse_sec(&[0]),
+            Err(CryptoError::InfinityPublicKey)
+        );
+    }
+
+    #[test]
+    fn sign() {
+        // e = hash256("my secret"), z = hash256("my message"), k = 1234567890
+        let e = hex("8b387de39861728c92ec9f589c303b1038ff60eb3963b12cd212263a1d1e0f00");
+        let z = hex("0231c6f3d980a6b0fb7152f85cee7eb52bf92433d9919b9c5218cb08e79cce78");
+        let key = PrivateKey::new(e);
+
+        assert_eq!(
+            key.public_key.point,
+            point(
+                "028d003eab2e428d11983f3e97c3fa0addf3b42740df0d211795ffb3be2f6c52",
+                "0ae987b9ec6ea159c78cb2a937ed89096fb218d9e7594f02b547526d8cd309e2",
+            )
+        );
+
+        let sig = key.sign_with_nonce(z, U256::from(1234567890)).unwrap();
+        assert_eq!(
+            sig.r,
+            hex("2b698a0f0a4041b77e63488ad48c23e8e8838dd1fb7520408b121697b782ef22")
+        );
+        assert_eq!(
+            sig.s,
+            hex("44eb19fd1061c078d1da052cd7b994c9d43b916c9f7b4789d46f0a44d087b488")
+        );
+        assert!(key.public_key.verify(z, &sig));
+
+        let sig = key.sign(z);
+        assert!(key.public_key.verify(z, &sig));
+    }
+
+    // PrivateKey / PublicKey は曲線によらない SigningKey / VerifyingKey に low-S を足しただけ
+    #[test]
+    fn generic_key() {
+        let secret = hex("8b387de39861728c92ec9f589c303b1038ff60eb3963b12cd212263a1d1e0f00");
+        let key = PrivateKey::new(secret);
+        let generic = SigningKey::<Secp256k1>::new(secret);
+        assert_eq!(generic.verifying_key.point, key.public_key.point.0);
+
+        for message in [&b"Satoshi Nakamoto"[..], b"Alan Turing", b"my message"] {
+            let z = make_hash(message);
+            let sig = generic.sign(z);
+            assert_eq!(Secp256k1::hash(message), z);
+            assert_eq!(key.sign(z), sig.normalize_s());
+            assert!(key.public_key.verify(z, &sig));
+            assert_eq!(key.public_key.verify_strict(z, &sig), sig.is_low_s());
+        }
+    }
+
+    #[test]
+    fn sign_deterministic() {
+        let key = PrivateKey::new(U256::one());
+        let z = make_hash(b"Satoshi Nakamoto");
+        let sig = key.sign(z);
+
+        assert_eq!(
+            sig.r,
+            hex("934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8")
+        );
+        assert_eq!(
+            sig.s,
+            hex("2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5")
+        );
+        assert_eq!(key.sign(z), sig);
+        assert!(key.public_key.verify(z, &sig));
+
+        let key = PrivateKey::new(hex(
+            "f8b8af8ce3c7cca5e300d33939540c10d45ce001b8f252bfbc57ba0342904181",
+        ));
+        let z = make_hash(b"Alan Turing");
+        let sig = key.sign(z);
+
+        assert_eq!(
+            sig.r,
+            hex("7063ae83e7f62bbb171798131b4a0564b956930092b33b07b395615d9ec7e15c")
+        );
+        assert_eq!(
+            sig.s,
+            hex("58dfcc1e00a35e1572f366ffe34ba0fc47db1e7189759b9fb233c5b05ab388ea")
+        );
+    }
+
+    #[test]
+    fn low_s() {
+        let key = PublicKey::new(point(
+            "887387e452b8eacc4acfde10d9aaf7f6d9a0f975aabb10d006e4da568744d06c",
+            "61de6d95231cd89026e286df3b6ae4a894a3378e393e93a0f45b666329a0ae34",
+        ))
+        .unwrap();
+        let z = hex("7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d");
+        let high = Signature {
+            r: hex("eff69ef2b1bd93a66ed5219add4fb51e11a840f404876325a1e8ffe0529a2c"),
+            s: hex("c7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6"),
+        };
+        let low = high.normalize_s();
+
+        assert!(!high.is_low_s());
+        assert!(low.is_low_s());
+        assert_eq!(
+            low.s,
+            hex("38df8011e682d839e75159debf909408cb3f12ae472b1d88cf6280cf01c6568b")
+        );
+        assert_eq!(low.normalize_s(), low);
+        assert!(key.verify(z, &high));
+        assert!(key.verify(z, &low));
+        assert!(!key.verify_strict(z, &high));
+        assert!(key.verify_strict(z, &low));
+
+        // 署名は常に low-S で出てくる
+        let key = PrivateKey::new(U256::from(12345));
+        for message in [&b"a"[..], b"b", b"c", b"d"] {
+            let z = make_hash(message);
+            assert!(key.public_key.verify_strict(z, &key.sign(z)));
+        }
+    }
+
+    #[test]
+    fn recover() {
+        let e = hex("8b387de39861728c92ec9f589c303b1038ff60eb3963b12cd212263a1d1e0f00");
+        let z = hex("0231c6f3d980a6b0fb7152f85cee7eb52bf92433d9919b9c5218cb08e79cce78");
+        let key = PrivateKey::new(e);
+
+        let sig = key
+            .sign_recoverable_with_nonce(z, U256::from(1234567890))
+            .unwrap();
+        assert_eq!(
+            sig.signature(),
+            key.sign_with_nonce(z, U256::from(1234567890)).unwrap()
+        );
+        assert_eq!(sig.recovery_id, 1);
+        assert_eq!(recover_public_key(z, &sig), Ok(key.public_key.point));
+
+        // 別の recovery_id からは別の鍵が出てくる
+        let other = RecoverableSignature {
+            recovery_id: 0,
+            ..sig
+        };
+        let wrong = recover_public_key(z, &other).unwrap();
+        assert_ne!(wrong, key.public_key.point);
+        assert!(PublicKey::new(wrong).unwrap().verify(z, &sig.signature()));
+
+        for secret in [1u64, 2, 12345, 0xdeadbeef] {
+            let key = PrivateKey::new(U256::from(secret));
+            for message in [&b"Satoshi Nakamoto"[..], b"Alan Turing"] {
+                let z = make_hash(message);
+                let sig = key.sign_recoverable(z);
+                assert_eq!(sig.signature(), key.sign(z));
+                assert_eq!(recover_public_key(z, &sig), Ok(key.public_key.point));
+            }
+        }
+    }
+
+    #[test]
+    fn recover_invalid() {
+        let key = PrivateKey::new(U256::from(12345));
+        let z = make_hash(b"message");
+        let sig = key.sign_recoverable(z);
+
+        let cases = [
+            RecoverableSignature {
+                r: U256::zero(),
+                ..sig
+            },
+            RecoverableSignature { s: N, ..sig },
+            RecoverableSignature {
+                recovery_id: 4,
+                ..sig
+            },
+            // r + n >= p
+            RecoverableSignature {
+                recovery_id: sig.recovery_id | 2,
+                ..sig
+            },
+        ];
+        for case in cases {
+            assert_eq!(recover_public_key(z, &case), Err(CryptoError::OutOfRange));
+        }
+        // x = 5 は曲線上の点の x 座標ではない
+        let no_point = RecoverableSignature {
+            r: U256::from(5),
+            ..sig
+        };
+        assert_eq!(
+            recover_public_key(z, &no_point),
+            Err(CryptoError::NotOnCurve)
+        );
+    }
+
+    #[test]
+    fn der() {
+        let sig = Signature::<Secp256k1> {
+            r: hex("37206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c6"),
+            s: hex("8ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec"),
+        };
+        let der = ::hex::decode(
+            "3045022037206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c60221008ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec",
+        )
+        .unwrap();
+
+        assert_eq!(sig.der(), der);
+        assert_eq!(Signature::parse_der(&der), Ok(sig));
+
+        let small = Signature::<Secp256k1> {
+            r: U256::from(1),
+            s: U256::from(0x80),
+        };
+        assert_eq!(
+            small.der(),
+            vec![0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80]
+        );
+        assert_eq!(Signature::parse_der(&small.der()), Ok(small));
+    }
+
+    #[test]
+    fn parse_der_invalid() {
+        let invalid = |der: &[u8], e| {
+            assert_eq!(
+                Signature::<Secp256k1>::parse_der(der),
+                Err(CryptoError::InvalidDer(e))
+            );
+        };
+        // 30 07 02 01 01 02 02 00 80 を少しずつ壊す
+        let valid = [0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80];
+
+        invalid(&valid[..7], DerError::InvalidLength);
+        invalid(&[0x30; 73], DerError::InvalidLength);
+        invalid(
+            &[0x31, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80],
+            DerError::NotSequence,
+        );
+        invalid(
+            &[0x30, 0x08, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80],
+            DerError::SequenceLengthMismatch,
+        );
+        invalid(
+            &[0x30, 0x07, 0x02, 0x04, 0x01, 0x02, 0x02, 0x00, 0x80],
+            DerError::IntegerLengthMismatch,
+        );
+        invalid(
+            &[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x01, 0x00, 0x80],
+            DerError::IntegerLengthMismatch,
+        );
+        invalid(
+            &[0x30, 0x07, 0x03, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80],
+            DerError::NotInteger,
+        );
+        invalid(
+            &[0x30, 0x07, 0x02, 0x01, 0x01, 0x04, 0x02, 0x00, 0x80],
+            DerError::NotInteger,
+        );
+        invalid(
+            &[0x30, 0x07, 0x02, 0x00, 0x02, 0x03, 0x00, 0x80, 0x01],
+            DerError::ZeroLengthInteger,
+        );
+        invalid(
+            &[0x30, 0x07, 0x02, 0x01, 0x81, 0x02, 0x02, 0x00, 0x80],
+            DerError::NegativeInteger,
+        );
+        invalid(
+            &[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x7f],
+            DerError::ExcessivePadding,
+        );
+        invalid(
+            &[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x80, 0x00],
+            DerError::NegativeInteger,
+        );
+
+        let mut too_large = vec![0x30, 0x26, 0x02, 0x21];
+        too_large.extend_from_slice(&[0x7f; 33]);
+        too_large.extend_from_slice(&[0x02, 0x01, 0x01]);
+        invalid(&too_large, DerError::IntegerTooLarge);
+    }
+
+    #[test]
+    fn try_new() {
+        assert!(PrivateKey::try_new(U256::from(12345)).is_ok());
+        assert_eq!(
+            PrivateKey::try_new(U256::zero()).err(),
+            Some(CryptoError::OutOfRange)
+        );
+        assert_eq!(PrivateKey::try_new(N).err(), Some(CryptoError::OutOfRange));
+    }
+}
+
+// 曲線によらない ECDSA。s は正規化せず、n - s の署名もそのまま受け付ける
+// (Bitcoin の low-S は secp256k1 の PrivateKey / PublicKey の側で扱う)
+pub trait EcdsaCurve:
+    Curve<Int: UInt, Field = Fp<<Self as Curve>::Int, <Self as EcdsaCurve>::FieldReduction>>
+{
+    // 座標の体の乗算。P-256 や P-521 は p の形に合わせた簡約を使う
+    type FieldReduction: Reduction<<Self as Curve>::Int>;
+    // ES256 / ES384 / ES512 と同じく、曲線の大きさに合ったハッシュを使う
+    type Digest: Digest + BlockSizeUser;
+
+    // bits2int(H(m)): ハッシュ値の先頭 qlen ビットを z とする
+    fn hash(message: &[u8]) -> Self::Int {
+        let digest = Self::Digest::digest(message);
+        let qlen = Self::N.bits();
+        let len = digest.len().min(qlen.div_ceil(8));
+        let z = Self::Int::from(&digest[..len]);
+        if len * 8 > qlen {
+            z >> (len * 8 - qlen)
+        } else {
+            z
+        }
+    }
+}
+
+impl EcdsaCurve for Secp256k1 {
+    type FieldReduction = GenericReduction;
+    type Digest = Sha256;
+}
+
+impl EcdsaCurve for Secp256r1 {
+    type FieldReduction = P256Reduction;
+    type Digest = Sha256;
+}
+
+impl EcdsaCurve for Secp384r1 {
+    type FieldReduction = GenericReduction;
+    type Digest = Sha384;
+}
+
+impl EcdsaCurve for Secp521r1 {
+    type FieldReduction = P521Reduction;
+    type Digest = Sha512;
+}
+
+// 座標 1 つぶんのバイト数 (P-521 は 66 バイト)
+fn field_len<C: EcdsaCurve>() -> usize {
+    C::P.bits().div_ceil(8)
+}
+
+// 公開鍵は無限遠点にならない。O だと u*G だけで R が作れ、誰でも署名を偽造できるので、
+// 作るときと読み込むときに弾いておく
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VerifyingKey<C: Curve> {
+    point: Point<C>,
+}
+
+impl<C: EcdsaCurve> VerifyingKey<C> {
+    pub fn new(point: Point<C>) -> Result<Self, CryptoError> {
+        match point {
+            Point::Coordinate { .. } => Ok(Self { point }),
+            Point::Infinity => Err(CryptoError::InfinityPublicKey),
+        }
+    }
+
+    pub fn point(&self) -> Point<C> {
+        self.point
+    }
+
+    // SEC1: 04 || x || y か (02 | 03) || x。無限遠点 (00) は公開鍵として受け付けない
+    pub fn parse_sec(sec: &[u8]) -> Result<Self, CryptoError> {
+        let len = field_len::<C>();
+        let field = |bytes: &[u8]| C::Field::try_new(C::Int::from(bytes), C::P);
+        let point = match sec.first() {
+            Some(0) if sec.len() == 1 => return Err(CryptoError::InfinityPublicKey),
+            Some(4) if sec.len() == 2 * len + 1 => {
+                Point::try_new(field(&sec[1..len + 1])?, field(&sec[len + 1..])?)?
+            }
+            Some(2 | 3) if sec.len() == len + 1 => {
+                let x = field(&sec[1..])?;
+                let beta = (x * x * x + C::A * x + C::B)
+                    .sqrt()
+                    .ok_or(CryptoError::NotOnCurve)?;
+                let y = if beta.num.bit(0) == (sec[0] == 3) {
+                    beta
+                } else {
+                    -beta
+                };
+                Point::Coordinate { x, y }
+            }
+            _ => return Err(CryptoError::InvalidEncoding),
+        };
+        Self::new(point)
+    }
+
+    // 非圧縮の SEC1 形式 04 || x || y
+    pub fn sec(&self) -> Vec<u8> {
+        let len = field_len::<C>();
+        let mut ret = vec![4];
+        if let Point::Coordinate { x, y } = self.point {
+            ret.extend(x.num.to_be_bytes(len));
+            ret.extend(y.num.to_be_bytes(len));
+        }
+        ret
+    }
+
+    // u = z/s, v = r/s として (u*G + v*Q).x mod n == r
+    pub fn verify(&self, z: C::Int, sig: &Signature<C>) -> bool {
+        let n = C::N;
+        if sig.r.is_zero() || sig.r >= n || sig.s.is_zero() || sig.s >= n {
+            return false;
+        }
+        let scalar = |num: C::Int| FieldElement::new(num % n, n);
+        let (z, r, s) = (scalar(z), scalar(sig.r), scalar(sig.s));
+        let total = Point::multi_mul(&[((z / s).num, C::G), ((r / s).num, self.point)]);
+
+        match total {
+            Point::Coordinate { x, .. } => x.num % n == sig.r,
+            Point::Infinity => false,
+        }
+    }
+
+    // メッセージを曲線のハッシュ関数で z にしてから検証する
+    pub fn verify_message(&self, message: &[u8], sig: &Signature<C>) -> bool {
+        self.verify(C::hash(message), sig)
+    }
+}
+
+pub struct SigningKey<C: Curve> {
+    secret: C::Int,
+    pub verifying_key: VerifyingKey<C>,
+}
+
+impl<C: EcdsaCurve> SigningKey<C> {
+    pub fn new(secret: C::Int) -> Self {
+        if secret.is_zero() || secret >= C::N {
+            panic!("Secret {:?} not in range 1 to {:?}", secret, C::N)
+        }
+        // secret は 1..n なので secret*G は無限遠点にならない
+        Self {
+            secret,
+            verifying_key: VerifyingKey {
+                point: C::G.mul_secret(secret),
+            },
+        }
+    }
+
+    pub fn try_new(secret: C::Int) -> Result<Self, CryptoError> {
+        if secret.is_zero() || secret >= C::N {
+            return Err(CryptoError::OutOfRange);
+        }
+        Ok(Self::new(secret))
+    }
+
+    // k は RFC 6979 で秘密鍵と z から決定的に作る
+    pub fn sign(&self, z: C::Int) -> Signature<C> {
+        Rfc6979::<C::Int, C::Digest>::with_digest(self.secret, z, C::N)
+            .find_map(|k| self.sign_with_nonce(z, k))
+            .unwrap()
+    }
+
+    pub fn sign_message(&self, message: &[u8]) -> Signature<C> {
+        self.sign(C::hash(message))
+    }
+
+    // k は秘密なので長さを固定したラダーで k*G を求める
+    pub fn sign_with_nonce(&self, z: C::Int, k: C::Int) -> Option<Signature<C>> {
+        if k.is_zero() || k >= C::N {
+            return None;
+        }
+        self.sign_with_point(z, k, C::G.mul_secret(k))
+    }
+
+    // r = R.x mod n, s = (z + r*d)/k (R = k*G)
+    fn sign_with_point(&self, z: C::Int, k: C::Int, point: Point<C>) -> Option<Signature<C>> {
+        let n = C::N;
+        let x = match point {
+            Point::Coordinate { x, .. } => x.num,
+            Point::Infinity => return None,
+        };
+        let scalar = |num: C::Int| FieldElement::new(num % n, n);
+        let r = scalar(x);
+        let s = (scalar(z) + r * scalar(self.secret)) / scalar(k);
+        if r.num.is_zero() || s.num.is_zero() {
+            return None;
+        }
+        Some(Signature { r: r.num, s: s.num })
+    }
+}
+
+#[cfg(test)]
+mod p256_ecdsa_tests {
+    use super::*;
+    use crate::secp256r1::{P256Field, Secp256r1, N};
+
+    fn hex(s: &str) -> U256 {
+        U256::from_str_radix(s, 16).unwrap()
+    }
+
+    #[test]
+    fn rfc6979() {
+        // RFC 6979 A.2.5 (P-256, SHA-256)
+        let key = SigningKey::<Secp256r1>::new(hex(
+            "C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721",
+        ));
+        let mut sec = vec![4];
+        sec.extend(
+            ::hex::decode("60FED4BA255A9D31C961EB74C6356D68C049B8923B61FA6CE669622E60F29FB6")
+                .unwrap(),
+        );
+        sec.extend(
+            ::hex::decode("7903FE1008B8BC99A41AE9E95628BC64F2F1B20C2D7E9F5177A3C294D4462299")
+                .unwrap(),
+        );
+        assert_eq!(VerifyingKey::parse_sec(&sec), Ok(key.verifying_key));
+
+        let cases = [
+            (
+                &b"sample"[..],
+                "EFD48B2AACB6A8FD1140DD9CD45E81D69D2C877B56AAF991C34D0EA84EAF3716",
+                "F7CB1C942D657C41D436C7A1B6E29F65F3E900DBB9AFF4064DC4AB2F843ACDA8",
+            ),
+            (
+                &b"test"[..],
+                "F1ABB023518351CD71D881567B1EA663ED3EFCF6C5132B354F28D3B0B7D38367",
+                "019F4113742A2B14BD25926B49C649155F267E60D3814B4C0CC84250E46F0083",
+            ),
+        ];
+        for (message, r, s) in cases {
+            let z = make_hash(message);
+            let sig = key.sign(z);
+            assert_eq!(
+                sig,
+                Signature {
+                    r: hex(r),
+                    s: hex(s)
+                }
+            );
+            assert!(key.verifying_key.verify(z, &sig));
+            // P-256 では high-S の署名もそのまま有効
+            assert!(key.verifying_key.verify(
+                z,
+                &Signature {
+                    r: sig.r,
+                    s: N - sig.s
+                }
+            ));
+            assert!(!key.verifying_key.verify(z + 1, &sig));
+        }
+    }
+
+    #[test]
+    fn nist_siggen() {
+        let vectors = include_str!("../testdata/p256_siggen.txt");
+        for line in vectors.lines().filter(|l| !l.starts_with('#')) {
+            let v: Vec<U256> = line.split(' ').map(hex).collect();
+            let key = SigningKey::<Secp256r1>::new(v[0]);
+            let point = Point::new(
+                P256Field::new(v[1], Secp256r1::P),
+                P256Field::new(v[2], Secp256r1::P),
+            );
+            assert_eq!(key.verifying_key.point, point);
+
+            let sig = key.sign_with_nonce(v[4], v[3]).unwrap();
+            assert_eq!(sig, Signature { r: v[5], s: v[6] });
+            assert!(key.verifying_key.verify(v[4], &sig));
+        }
+    }
+
+    #[test]
+    fn wycheproof() {
+        let vectors = include_str!("../testdata/wycheproof_p256.txt");
+        let mut key = None;
+        let mut count = 0;
+        for line in vectors.lines().filter(|l| !l.starts_with('#')) {
+            let fields: Vec<&str> = line.split(' ').collect();
+            let bytes = |s: &str| {
+                if s == "-" {
+                    vec![]
+                } else {
+                    ::hex::decode(s).unwrap()
+                }
+            };
+            if fields[0] == "key" {
+                let mut sec = vec![4];
+                sec.extend(bytes(fields[1]));
+                sec.extend(bytes(fields[2]));
+                key = Some(VerifyingKey::<Secp256r1>::parse_sec(&sec).unwrap());
+                continue;
+            }
+            let valid = fields[2] == "valid";
+            let z = make_hash(&bytes(fields[0]));
+            let result = match Signature::<Secp256r1>::parse_der(&bytes(fields[1])) {
+                Ok(sig) => key.unwrap().verify(z, &sig),
+                Err(_) => false,
+            };
+            assert_eq!(result, valid, "{}", line);
+            count += 1;
+        }
+        assert_eq!(count, 386);
+    }
+
+    #[test]
+    fn parse_sec() {
+        let key = SigningKey::<Secp256r1>::new(U256::from(12345)).verifying_key;
+        let (x, y) = match key.point {
+            Point::Coordinate { x, y } => (x.num, y.num),
+            Point::Infinity => unreachable!(),
+        };
+        let mut compressed = vec![2 + y.bit(0) as u8];
+        compressed.extend_from_slice(&to_bytes(x));
+        assert_eq!(VerifyingKey::parse_sec(&compressed), Ok(key));
+        compressed[0] ^= 1;
+        assert_eq!(
+            VerifyingKey::parse_sec(&compressed),
+            VerifyingKey::new(-key.point)
+        );
+
+        let mut uncompressed = vec![4];
+        uncompressed.extend_from_slice(&to_bytes(x));
+        uncompressed.extend_from_slice(&to_bytes(y + 1));
+        assert_eq!(
+            VerifyingKey::<Secp256r1>::parse_sec(&uncompressed),
+            Err(CryptoError::NotOnCurve)
+        );
+        assert_eq!(
+            VerifyingKey::<Secp256r1>::parse_sec(&uncompressed[..33]),
+            Err(CryptoError::InvalidEncoding)
+        );
+    }
+
+    #[test]
+    fn try_new() {
+        assert!(SigningKey::<Secp256r1>::try_new(U256::from(12345)).is_ok());
+        assert_eq!(
+            SigningKey::<Secp256r1>::try_new(N).err(),
+            Some(CryptoError::OutOfRange)
+        );
+    }
+}
+
+#[cfg(test)]
+mod nist_ecdsa_tests {
+    use super::*;
+    use crate::field::U576;
+    use primitive_types::U512;
+
+    fn int<T: UInt>(s: &str) -> T {
+        T::from(&::hex::decode(s).unwrap()[..])
+    }
+
+    // FIPS 186-4 SigGen: d Qx Qy k H(Msg) r s
+    fn siggen<C: EcdsaCurve>(vectors: &str) {
+        for line in vectors.lines().filter(|l| !l.starts_with('#')) {
+            let v: Vec<&str> = line.split(' ').collect();
+            let key = SigningKey::<C>::new(int(v[0]));
+            let sec = ::hex::decode(format!("04{}{}", v[1], v[2])).unwrap();
+            assert_eq!(VerifyingKey::parse_sec(&sec), Ok(key.verifying_key));
+            assert_eq!(key.verifying_key.sec(), sec);
+
+            let z: C::Int = int(v[4]);
+            let sig = key.sign_with_nonce(z, int(v[3])).unwrap();
+            assert_eq!(
+                sig,
+                Signature {
+                    r: int(v[5]),
+                    s: int(v[6])
+                }
+            );
+            assert!(key.verifying_key.verify(z, &sig));
+            assert!(!key.verifying_key.verify(z + C::Int::from(&[1][..]), &sig));
+        }
+    }
+
+    // RFC 6979 の "sample" と "test" に対する (r, s)
+    fn rfc6979<C: EcdsaCurve>(key: &SigningKey<C>, cases: [(&[u8], &str, &str); 2]) {
+        for (message, r, s) in cases {
+            let sig = key.sign_message(message);
+            assert_eq!(
+                sig,
+                Signature {
+                    r: int(r),
+                    s: int(s)
+                }
+            );
+            assert!(key.verifying_key.verify_message(message, &sig));
+            assert!(!key.verifying_key.verify_message(b"other", &sig));
+        }
+    }
+
+    #[test]
+    fn p384_siggen() {
+        siggen::<Secp384r1>(include_str!("../testdata/p384_siggen.txt"));
+    }
+
+    #[test]
+    fn p521_siggen() {
+        siggen::<Secp521r1>(include_str!("../testdata/p521_siggen.txt"));
+    }
+
+    #[test]
+    fn p384_rfc6979() {
+        // RFC 6979 A.2.6 (P-384, SHA-384)
+        let key = SigningKey::<Secp384r1>::new(int(
+            "6B9D3DAD2E1B8C1C05B19875B6659F4DE23C3B667BF297BA9AA47740787137D896D5724E4C70A825F872C9EA60D2EDF5",
+        ));
+        let sec = ::hex::decode(concat!(
+            "04",
+            "EC3A4E415B4E19A4568618029F427FA5DA9A8BC4AE92E02E06AAE5286B300C64DEF8F0EA9055866064A254515480BC13",
+            "8015D9B72D7D57244EA8EF9AC0C621896708A59367F9DFB9F54CA84B3F1C9DB1288B231C3AE0D4FE7344FD2533264720",
+        ))
+        .unwrap();
+        assert_eq!(key.verifying_key.sec(), sec);
+
+        rfc6979(
+            &key,
+            [
+                (
+                    b"sample",
+                    "94EDBB92A5ECB8AAD4736E56C691916B3F88140666CE9FA73D64C4EA95AD133C81A648152E44ACF96E36DD1E80FABE46",
+                    "99EF4AEB15F178CEA1FE40DB2603138F130E740A19624526203B6351D0A3A94FA329C145786E679E7B82C71A38628AC8",
+                ),
+                (
+                    b"test",
+                    "8203B63D3C853E8D77227FB377BCF7B7B772E97892A80F36AB775D509D7A5FEB0542A7F0812998DA8F1DD3CA3CF023DB",
+                    "DDD0760448D42D8A43AF45AF836FCE4DE8BE06B485E9B61B827C2F13173923E06A739F040649A667BF3B828246BAA5A5",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn p521_rfc6979() {
+        // RFC 6979 A.2.7 (P-521, SHA-512)。値は 66 バイトに 0 を詰めている
+        let key = SigningKey::<Secp521r1>::new(int(
+            "00FAD06DAA62BA3B25D2FB40133DA757205DE67F5BB0018FEE8C86E1B68C7E75CAA896EB32F1F47C70855836A6D16FCC1466F6D8FBEC67DB89EC0C08B0E996B83538",
+        ));
+        let sec = ::hex::decode(concat!(
+            "04",
+            "01894550D0785932E00EAA23B694F213F8C3121F86DC97A04E5A7167DB4E5BCD371123D46E45DB6B5D5370A7F20FB633155D38FFA16D2BD761DCAC474B9A2F5023A4",
+            "00493101C962CD4D2FDDF782285E64584139C2F91B47F87FF82354D6630F746A28A0DB25741B5B34A828008B22ACC23F924FAAFBD4D33F81EA66956DFEAA2BFDFCF5",
+        ))
+        .unwrap();
+        assert_eq!(key.verifying_key.sec(), sec);
+
+        rfc6979(
+            &key,
+            [
+                (
+                    b"sample",
+                    "00C328FAFCBD79DD77850370C46325D987CB525569FB63C5D3BC53950E6D4C5F174E25A1EE9017B5D450606ADD152B534931D7D4E8455CC91F9B15BF05EC36E377FA",
+                    "00617CCE7CF5064806C467F678D3B4080D6F1CC50AF26CA209417308281B68AF282623EAA63E5B5C0723D8B8C37FF0777B1A20F8CCB1DCCC43997F1EE0E44DA4A67A",
+                ),
+                (
+                    b"test",
+                    "013E99020ABF5CEE7525D16B69B229652AB6BDF2AFFCAEF38773B4B7D08725F10CDB93482FDCC54EDCEE91ECA4166B2A7C6265EF0CE2BD7051B7CEF945BABD47EE6D",
+                    "01FBD0013C674AA79CB39849527916CE301C66EA7CE8B80682786AD60F98F7E78A19CA69EFF5C57400E3B3A0AD66CE0978214D13BAF4E9AC60752F7B155E2DE4DCE3",
+                ),
+            ],
+        );
+    }
+
+    fn der_round_trip<C: EcdsaCurve>(secret: C::Int) {
+        let key = SigningKey::<C>::new(secret);
+        for message in [&b"sample"[..], b"test"] {
+            let sig = key.sign_message(message);
+            let der = sig.der();
+            assert_eq!(Signature::<C>::parse_der(&der), Ok(sig));
+            assert!(key.verifying_key.verify_message(message, &sig));
+        }
+    }
+
+    #[test]
+    fn der() {
+        der_round_trip::<Secp384r1>(U512::from(12345));
+        der_round_trip::<Secp521r1>(U576::from(12345));
+
+        // 66 バイトの r, s が入る P-521 の署名は中身が 128 バイトを超え、長さを 0x81 len で書く
+        let sig = Signature::<Secp521r1> {
+            r: Secp521r1::N - 1,
+            s: Secp521r1::N >> 1,
+        };
+        let der = sig.der();
+        assert_eq!(der[..3], [0x30, 0x81, 136]);
+        assert_eq!(Signature::parse_der(&der), Ok(sig));
+
+        // 小さい値なら P-521 でも短い形
+        let small = Signature::<Secp521r1> {
+            r: U576::one(),
+            s: U576::from(0x80),
+        };
+        let der = small.der();
+        assert_eq!(der, [0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80]);
+        assert_eq!(Signature::parse_der(&der), Ok(small));
+    }
+
+    #[test]
+    fn parse_der_invalid() {
+        let invalid = |e| Some(CryptoError::InvalidDer(e));
+
+        // 短い形で書ける長さを 0x81 len で書いたもの
+        let long_form = [0x30, 0x81, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80];
+        assert_eq!(
+            Signature::<Secp521r1>::parse_der(&long_form).err(),
+            invalid(DerError::SequenceLengthMismatch)
+        );
+
+        // P-384 の整数は 48 バイトまで
+        let mut too_large = vec![0x30, 0x36, 0x02, 0x31];
+        too_large.extend([0x7f; 49]);
+        too_large.extend([0x02, 0x01, 0x01]);
+        assert_eq!(
+            Signature::<Secp384r1>::parse_der(&too_large).err(),
+            invalid(DerError::IntegerTooLarge)
+        );
+        assert_eq!(
+            Signature::<Secp521r1>::parse_der(&too_large).map(|sig| sig.s),
+            Ok(U576::one())
+        );
+
+        // 長さの上限は曲線の大きさで決まる
+        let sig = Signature::<Secp521r1> {
+            r: Secp521r1::N - 1,
+            s: Secp521r1::N - 1,
+        };
+        assert_eq!(
+            Signature::<Secp384r1>::parse_der(&sig.der()).err(),
+            invalid(DerError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn hash() {
+        // P-521 の n は 521 ビットなので SHA-512 はそのまま z になる
+        let digest = Sha512::digest(b"sample");
+        assert_eq!(Secp521r1::hash(b"sample"), U576::from(&digest[..]));
+        assert_eq!(
+            Secp384r1::hash(b"sample"),
+            U512::from(&Sha384::digest(b"sample")[..])
+        );
+        assert_eq!(Secp256r1::hash(b"sample"), make_hash(b"sample"));
+    }
+
+    #[test]
+    fn parse_sec() {
+        let key = SigningKey::<Secp521r1>::new(U576::from(12345)).verifying_key;
+        let sec = key.sec();
+        assert_eq!(sec.len(), 133);
+        let mut compressed = vec![2 + (sec[132] & 1)];
+        compressed.extend_from_slice(&sec[1..67]);
+        assert_eq!(VerifyingKey::parse_sec(&compressed), Ok(key));
+
+        // P-256 の長さのものは受け付けない
+        assert_eq!(
+            VerifyingKey::<Secp521r1>::parse_sec(&sec[..65]),
+            Err(CryptoError::InvalidEncoding)
+        );
+        assert_eq!(
+            VerifyingKey::<Secp384r1>::parse_sec(&sec[..67]),
+            Err(CryptoError::InvalidEncoding)
+        );
+        assert_eq!(
+            SigningKey::<Secp521r1>::try_new(Secp521r1::N).err(),
+            Some(CryptoError::OutOfRange)
+        );
+    }
+}
@@ -0,0 +1,378 @@
+use crate::edwards25519::{scalar_from_le_bytes, EdwardsPoint, L};
+use crate::error::CryptoError;
+use primitive_types::U256;
+use rand::Rng;
+use sha2::{Digest, Sha512};
+
+// RFC 8032 の Ed25519 (5.1)。Ed25519ctx と Ed25519ph は dom2(F, C) を前に付けてハッシュする
+const DOM2_PREFIX: &[u8] = b"SigEd25519 no Ed25519 collisions";
+
+// dom2(F, C) = "SigEd25519 no Ed25519 collisions" || F || len(C) || C。
+// 文脈は 255 バイトまで
+fn dom2(flag: u8, context: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if context.len() > 255 {
+        return Err(CryptoError::OutOfRange);
+    }
+    let mut ret = DOM2_PREFIX.to_vec();
+    ret.push(flag);
+    ret.push(context.len() as u8);
+    ret.extend_from_slice(context);
+    Ok(ret)
+}
+
+// Ed25519ctx は空の文脈を許さない (空なら素の Ed25519 を使う)
+fn dom2_ctx(context: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if context.is_empty() {
+        return Err(CryptoError::OutOfRange);
+    }
+    dom2(0, context)
+}
+
+// SHA-512(dom || data...) を L で割った余り
+fn hash_scalar(dom: &[u8], data: &[&[u8]]) -> U256 {
+    let mut hasher = Sha512::new();
+    hasher.update(dom);
+    for d in data {
+        hasher.update(d);
+    }
+    scalar_from_le_bytes(&hasher.finalize()).num
+}
+
+fn to_le_bytes(num: U256) -> [u8; 32] {
+    let mut ret = [0u8; 32];
+    num.to_little_endian(&mut ret);
+    ret
+}
+
+// R (点の符号化) || S (リトルエンディアン)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Signature {
+    pub r: [u8; 32],
+    pub s: U256,
+}
+
+impl Signature {
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut ret = [0u8; 64];
+        ret[..32].copy_from_slice(&self.r);
+        ret[32..].copy_from_slice(&to_le_bytes(self.s));
+        ret
+    }
+
+    // S の範囲は検証時に確かめる
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CryptoError> {
+        if bytes.len() != 64 {
+            return Err(CryptoError::InvalidEncoding);
+        }
+        Ok(Self {
+            r: bytes[..32].try_into().unwrap(),
+            s: U256::from_little_endian(&bytes[32..]),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VerifyingKey {
+    pub point: EdwardsPoint,
+    // 符号化した A はハッシュの入力になるので覚えておく
+    bytes: [u8; 32],
+}
+
+impl VerifyingKey {
+    pub fn new(point: EdwardsPoint) -> Self {
+        Self {
+            point,
+            bytes: point.compress(),
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, CryptoError> {
+        Ok(Self {
+            point: EdwardsPoint::decompress(bytes)?,
+            bytes: *bytes,
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+
+    pub fn verify(&self, message: &[u8], sig: &Signature) -> bool {
+        self.verify_with_dom(&[], message, sig)
+    }
+
+    pub fn verify_ctx(&self, message: &[u8], context: &[u8], sig: &Signature) -> bool {
+        match dom2_ctx(context) {
+            Ok(dom) => self.verify_with_dom(&dom, message, sig),
+            Err(_) => false,
+        }
+    }
+
+    // Ed25519ph: メッセージの代わりに SHA-512(M) に署名したもの
+    pub fn verify_ph(&self, message: &[u8], context: &[u8], sig: &Signature) -> bool {
+        match dom2(1, context) {
+            Ok(dom) => self.verify_with_dom(&dom, &Sha512::digest(message), sig),
+            Err(_) => false,
+        }
+    }
+
+    // RFC 8032 5.1.7: S < L を確かめ、[8][S]B = [8]R + [8][k]A を調べる。
+    // k = SHA-512(dom || R || A || M) mod L
+    fn verify_with_dom(&self, dom: &[u8], message: &[u8], sig: &Signature) -> bool {
+        if sig.s >= L {
+            return false;
+        }
+        let r = match EdwardsPoint::decompress(&sig.r) {
+            Ok(r) => r,
+            Err(_) => return false,
+        };
+        let k = hash_scalar(dom, &[&sig.r, &self.bytes, message]);
+        let total = EdwardsPoint::multi_mul(&[(sig.s, EdwardsPoint::BASE), (k, -self.point)]);
+        (total + -r).mul_by_cofactor().is_identity()
+    }
+}
+
+pub struct SigningKey {
+    secret: [u8; 32],
+    // SHA-512(secret) の前半を加工したスカラー s と、ナンスに使う後半の prefix
+    scalar: U256,
+    prefix: [u8; 32],
+    pub verifying_key: VerifyingKey,
+}
+
+impl SigningKey {
+    // RFC 8032 5.1.5: 秘密鍵は 32 バイトの乱数そのもの
+    pub fn new(secret: [u8; 32]) -> Self {
+        let h = Sha512::digest(secret);
+        let mut s: [u8; 32] = h[..32].try_into().unwrap();
+        // 下位 3 ビットを落として 8 の倍数にし、ビット 254 を立てる
+        s[0] &= 0xF8;
+        s[31] &= 0x7F;
+        s[31] |= 0x40;
+        let scalar = U256::from_little_endian(&s);
+        Self {
+            secret,
+            scalar,
+            prefix: h[32..].try_into().unwrap(),
+            verifying_key: VerifyingKey::new(EdwardsPoint::BASE.mul_ladder(scalar, 255)),
+        }
+    }
+
+    pub fn generate() -> Self {
+        let mut secret = [0u8; 32];
+        rand::thread_rng().fill(&mut secret);
+        Self::new(secret)
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.secret
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        self.sign_with_dom(&[], message)
+    }
+
+    pub fn sign_ctx(&self, message: &[u8], context: &[u8]) -> Result<Signature, CryptoError> {
+        Ok(self.sign_with_dom(&dom2_ctx(context)?, message))
+    }
+
+    pub fn sign_ph(&self, message: &[u8], context: &[u8]) -> Result<Signature, CryptoError> {
+        Ok(self.sign_with_dom(&dom2(1, context)?, &Sha512::digest(message)))
+    }
+
+    // RFC 8032 5.1.6: r = SHA-512(dom || prefix || M) mod L, R = [r]B,
+    // S = (r + k*s) mod L。r は秘密なのでラダーで倍算する
+    fn sign_with_dom(&self, dom: &[u8], message: &[u8]) -> Signature {
+        let r = hash_scalar(dom, &[&self.prefix, message]);
+        let big_r = EdwardsPoint::BASE.mul_ladder(r, 253).compress();
+        let k = hash_scalar(dom, &[&big_r, &self.verifying_key.bytes, message]);
+
+        let scalar = |num: U256| scalar_from_le_bytes(&to_le_bytes(num));
+        let s = scalar(r) + scalar(k) * scalar(self.scalar);
+        Signature { r: big_r, s: s.num }
+    }
+}
+
+#[cfg(test)]
+mod ed25519_tests {
+    use super::*;
+
+    fn bytes<const N: usize>(s: &str) -> [u8; N] {
+        ::hex::decode(s).unwrap().try_into().unwrap()
+    }
+
+    fn key(secret: &str, public: &str) -> SigningKey {
+        let key = SigningKey::new(bytes(secret));
+        assert_eq!(key.verifying_key.to_bytes(), bytes::<32>(public));
+        assert_eq!(
+            VerifyingKey::from_bytes(&bytes(public)),
+            Ok(key.verifying_key)
+        );
+        key
+    }
+
+    fn signature(s: &str) -> Signature {
+        Signature::from_bytes(&::hex::decode(s).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn rfc8032_ed25519() {
+        // RFC 8032 7.1 TEST 1, 2, 3
+        let cases = [
+            (
+                "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+                "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+                "",
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+            ),
+            (
+                "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+                "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+                "72",
+                "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+            ),
+            (
+                "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+                "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+                "af82",
+                "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+            ),
+        ];
+        for (secret, public, message, sig) in cases {
+            let key = key(secret, public);
+            let message = ::hex::decode(message).unwrap();
+            let sig = signature(sig);
+
+            assert_eq!(key.sign(&message), sig);
+            assert!(key.verifying_key.verify(&message, &sig));
+            assert!(!key.verifying_key.verify(b"other", &sig));
+            // 素の Ed25519 の署名は ctx / ph としては通らない
+            assert!(!key.verifying_key.verify_ctx(&message, b"foo", &sig));
+            assert!(!key.verifying_key.verify_ph(&message, b"", &sig));
+        }
+    }
+
+    #[test]
+    fn rfc8032_ed25519ctx() {
+        // RFC 8032 7.2
+        let cases = [
+            (
+                "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+                "dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292",
+                "f726936d19c800494e3fdaff20b276a8",
+                "666f6f",
+                "55a4cc2f70a54e04288c5f4cd1e45a7bb520b36292911876cada7323198dd87a8b36950b95130022907a7fb7c4e9b2d5f6cca685a587b4b21f4b888e4e7edb0d",
+            ),
+            (
+                "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+                "dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292",
+                "f726936d19c800494e3fdaff20b276a8",
+                "626172",
+                "fc60d5872fc46b3aa69f8b5b4351d5808f92bcc044606db097abab6dbcb1aee3216c48e8b3b66431b5b186d1d28f8ee15a5ca2df6668346291c2043d4eb3e90d",
+            ),
+            (
+                "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+                "dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292",
+                "508e9e6882b979fea900f62adceaca35",
+                "666f6f",
+                "8b70c1cc8310e1de20ac53ce28ae6e7207f33c3295e03bb5c0732a1d20dc64908922a8b052cf99b7c4fe107a5abb5b2c4085ae75890d02df26269d8945f84b0b",
+            ),
+            (
+                "ab9c2853ce297ddab85c993b3ae14bcad39b2c682beabc27d6d4eb20711d6560",
+                "0f1d1274943b91415889152e893d80e93275a1fc0b65fd71b4b0dda10ad7d772",
+                "f726936d19c800494e3fdaff20b276a8",
+                "666f6f",
+                "21655b5f1aa965996b3f97b3c849eafba922a0a62992f73b3d1b73106a84ad85e9b86a7b6005ea868337ff2d20a7f5fbd4cd10b0be49a68da2b2e0dc0ad8960f",
+            ),
+        ];
+        for (secret, public, message, context, sig) in cases {
+            let key = key(secret, public);
+            let message = ::hex::decode(message).unwrap();
+            let context = ::hex::decode(context).unwrap();
+            let sig = signature(sig);
+
+            assert_eq!(key.sign_ctx(&message, &context), Ok(sig));
+            assert!(key.verifying_key.verify_ctx(&message, &context, &sig));
+            assert!(!key.verifying_key.verify_ctx(&message, b"baz", &sig));
+            assert!(!key.verifying_key.verify(&message, &sig));
+        }
+    }
+
+    #[test]
+    fn rfc8032_ed25519ph() {
+        // RFC 8032 7.3
+        let key = key(
+            "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42",
+            "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf",
+        );
+        let sig = signature("98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae4131f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406");
+
+        assert_eq!(key.sign_ph(b"abc", b""), Ok(sig));
+        assert!(key.verifying_key.verify_ph(b"abc", b"", &sig));
+        assert!(!key.verifying_key.verify_ph(b"abd", b"", &sig));
+        assert!(!key.verifying_key.verify_ph(b"abc", b"foo", &sig));
+        assert!(!key.verifying_key.verify(b"abc", &sig));
+
+        let sig = key.sign_ph(b"abc", b"foo").unwrap();
+        assert!(key.verifying_key.verify_ph(b"abc", b"foo", &sig));
+    }
+
+    #[test]
+    fn context() {
+        let key = SigningKey::generate();
+        assert_eq!(key.sign_ctx(b"message", b""), Err(CryptoError::OutOfRange));
+        assert_eq!(
+            key.sign_ctx(b"message", &[0; 256]),
+            Err(CryptoError::OutOfRange)
+        );
+        assert_eq!(
+            key.sign_ph(b"message", &[0; 256]),
+            Err(CryptoError::OutOfRange)
+        );
+
+        let sig = key.sign_ctx(b"message", &[0; 255]).unwrap();
+        assert!(key.verifying_key.verify_ctx(b"message", &[0; 255], &sig));
+        assert!(!key.verifying_key.verify_ctx(b"message", &[0; 256], &sig));
+    }
+
+    #[test]
+    fn generate() {
+        let key = SigningKey::generate();
+        let sig = key.sign(b"message");
+        assert!(key.verifying_key.verify(b"message", &sig));
+        assert_eq!(
+            SigningKey::new(key.to_bytes()).verifying_key,
+            key.verifying_key
+        );
+        assert_ne!(SigningKey::generate().to_bytes(), key.to_bytes());
+    }
+
+    #[test]
+    fn verify_invalid() {
+        let key = SigningKey::new([7; 32]);
+        let sig = key.sign(b"message");
+        assert_eq!(Signature::from_bytes(&sig.to_bytes()), Ok(sig));
+        assert_eq!(
+            Signature::from_bytes(&sig.to_bytes()[..63]),
+            Err(CryptoError::InvalidEncoding)
+        );
+
+        // S + L でも同じ群の元になるが、S >= L は受け付けない (展性の防止)
+        let malleable = Signature {
+            s: sig.s + L,
+            ..sig
+        };
+        assert!(!key.verifying_key.verify(b"message", &malleable));
+
+        // R が点として復元できない
+        let mut bad_r = sig;
+        bad_r.r = [0; 32];
+        bad_r.r[0] = 2;
+        assert!(!key.verifying_key.verify(b"message", &bad_r));
+
+        let mut flipped = sig.to_bytes();
+        flipped[40] ^= 1;
+        let flipped = Signature::from_bytes(&flipped).unwrap();
+        assert!(!key.verifying_key.verify(b"message", &flipped));
+    }
+}
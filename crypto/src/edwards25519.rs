@@ -0,0 +1,448 @@
+use std::marker::PhantomData;
+use std::ops::{Add, Mul, Neg};
+
+use crate::error::CryptoError;
+use crate::field::{Bits, ConditionalSwap, FieldElement, Fp, Modular, Reduction};
+use primitive_types::{U256, U512};
+
+// edwards25519 (RFC 8032 5.1): -x^2 + y^2 = 1 + d*x^2*y^2 over F_p, p = 2^255 - 19。
+// Weierstrass 形式ではないので Curve / Point とは別の点の型を使う
+pub const P: U256 = U256([
+    0xFFFFFFFFFFFFFFED,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0x7FFFFFFFFFFFFFFF,
+]);
+
+// 基点 B の位数 L = 2^252 + 27742317777372353535851937790883648493。余因子は 8
+pub const L: U256 = U256([
+    0x5812631A5CF5D3ED,
+    0x14DEF9DEA2F79CD6,
+    0x0000000000000000,
+    0x1000000000000000,
+]);
+
+// 2^255 - 19 専用の乗算。積を下の reduce で 2^256 = 38 を使って簡約する
+#[derive(Clone, Copy, Debug)]
+pub struct Ed25519Reduction;
+
+impl Reduction<U256> for Ed25519Reduction {
+    fn mul_mod(a: U256, b: U256, modulus: U256) -> U256 {
+        debug_assert_eq!(modulus, P);
+        reduce(a.full_mul(b))
+    }
+}
+
+pub type Ed25519Field = Fp<U256, Ed25519Reduction>;
+
+const fn fe(limbs: [u64; 4]) -> Ed25519Field {
+    Fp {
+        num: U256(limbs),
+        prime: P,
+        reduction: PhantomData,
+    }
+}
+
+// d = -121665/121666
+pub const D: Ed25519Field = fe([
+    0x75EB4DCA135978A3,
+    0x00700A4D4141D8AB,
+    0x8CC740797779E898,
+    0x52036CEE2B6FFE73,
+]);
+
+// 加算の式で使う 2d
+const D2: Ed25519Field = fe([
+    0xEBD69B9426B2F159,
+    0x00E0149A8283B156,
+    0x198E80F2EEF3D130,
+    0x2406D9DC56DFFCE7,
+]);
+
+const ZERO: Ed25519Field = fe([0, 0, 0, 0]);
+const ONE: Ed25519Field = fe([1, 0, 0, 0]);
+
+// 拡張座標 (X : Y : Z : T)。x = X/Z, y = Y/Z, x*y = T/Z
+#[derive(Clone, Copy, Debug)]
+pub struct EdwardsPoint {
+    x: Ed25519Field,
+    y: Ed25519Field,
+    z: Ed25519Field,
+    t: Ed25519Field,
+}
+
+impl EdwardsPoint {
+    // 単位元 (0, 1)
+    pub const IDENTITY: Self = Self {
+        x: ZERO,
+        y: ONE,
+        z: ONE,
+        t: ZERO,
+    };
+
+    // 基点 B = (x, 4/5)、x は正 (偶数)
+    pub const BASE: Self = Self {
+        x: fe([
+            0xC9562D608F25D51A,
+            0x692CC7609525A7B2,
+            0xC0A4E231FDD6DC5C,
+            0x216936D3CD6E53FE,
+        ]),
+        y: fe([
+            0x6666666666666658,
+            0x6666666666666666,
+            0x6666666666666666,
+            0x6666666666666666,
+        ]),
+        z: ONE,
+        t: fe([
+            0x6DDE8AB3A5B7DDA3,
+            0x20F09F80775152F5,
+            0x66EA4E8E64ABE37D,
+            0x67875F0FD78B7665,
+        ]),
+    };
+
+    pub fn from_affine(x: Ed25519Field, y: Ed25519Field) -> Result<Self, CryptoError> {
+        let (xx, yy) = (x * x, y * y);
+        if yy - xx != ONE + D * xx * yy {
+            return Err(CryptoError::NotOnCurve);
+        }
+        Ok(Self {
+            x,
+            y,
+            z: ONE,
+            t: x * y,
+        })
+    }
+
+    pub fn to_affine(self) -> (Ed25519Field, Ed25519Field) {
+        let z_inv = self.z.inverse();
+        (self.x * z_inv, self.y * z_inv)
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    // RFC 8032 5.1.2: y を 255 ビットのリトルエンディアンで書き、最上位ビットに x の偶奇を入れる
+    pub fn compress(&self) -> [u8; 32] {
+        let (x, y) = self.to_affine();
+        let mut ret = [0u8; 32];
+        y.num.to_little_endian(&mut ret);
+        ret[31] |= (x.num.bit(0) as u8) << 7;
+        ret
+    }
+
+    // RFC 8032 5.1.3: x^2 = (y^2 - 1) / (d*y^2 + 1) の平方根のうち、偶奇が合う方を取る
+    pub fn decompress(bytes: &[u8; 32]) -> Result<Self, CryptoError> {
+        let sign = bytes[31] >> 7 == 1;
+        let mut y = *bytes;
+        y[31] &= 0x7F;
+        let y = Ed25519Field::try_new(U256::from_little_endian(&y), P)
+            .map_err(|_| CryptoError::InvalidEncoding)?;
+
+        let yy = y * y;
+        let x = ((yy - ONE) / (D * yy + ONE))
+            .sqrt()
+            .ok_or(CryptoError::NotOnCurve)?;
+        // x = 0 に負の符号は付けられない
+        if x.num.is_zero() && sign {
+            return Err(CryptoError::InvalidEncoding);
+        }
+        let x = if x.num.bit(0) == sign { x } else { -x };
+        Ok(Self {
+            x,
+            y,
+            z: ONE,
+            t: x * y,
+        })
+    }
+
+    // RFC 8032 5.1.4 の 2倍算 (a = -1)
+    pub fn double(self) -> Self {
+        let a = self.x * self.x;
+        let b = self.y * self.y;
+        let zz = self.z * self.z;
+        let c = zz + zz;
+        let h = a + b;
+        let xy = self.x + self.y;
+        let e = h - xy * xy;
+        let g = a - b;
+        let f = c + g;
+        Self {
+            x: e * f,
+            y: g * h,
+            z: f * g,
+            t: e * h,
+        }
+    }
+
+    // [8]P。小さい位数の成分を消すのに使う
+    pub fn mul_by_cofactor(self) -> Self {
+        self.double().double().double()
+    }
+
+    // 加算の式は例外のない完全な式なので、ラダーは加算と 2倍算をそのまま並べればよい。
+    // 上位 bits ビットを必ず全部処理し、ビットの値では分岐しない
+    pub fn mul_ladder<U: Bits>(self, scalar: U, bits: usize) -> Self {
+        let mut r0 = Self::IDENTITY;
+        let mut r1 = self;
+        for i in (0..bits).rev() {
+            let b = scalar.bit(i);
+            Self::conditional_swap(&mut r0, &mut r1, b);
+            r1 = r0 + r1;
+            r0 = r0.double();
+            Self::conditional_swap(&mut r0, &mut r1, b);
+        }
+        r0
+    }
+
+    // k1*P1 + k2*P2 + ... 。検証用でスカラーは公開値なので、ビットを見て分岐してよい。
+    // 2倍算は全項で共有する
+    pub fn multi_mul<U: Bits>(terms: &[(U, EdwardsPoint)]) -> Self {
+        let bits = terms.iter().map(|(k, _)| k.bits()).max().unwrap_or(0);
+        let mut ret = Self::IDENTITY;
+        for i in (0..bits).rev() {
+            ret = ret.double();
+            for (k, p) in terms {
+                if k.bit(i) {
+                    ret = ret + *p;
+                }
+            }
+        }
+        ret
+    }
+}
+
+// 射影座標なので Z を払って比べる
+impl PartialEq for EdwardsPoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.x * other.z == other.x * self.z && self.y * other.z == other.y * self.z
+    }
+}
+
+// RFC 8032 5.1.4 の加算 (a = -1)。同じ点どうしや単位元でもそのまま使える
+impl Add for EdwardsPoint {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let a = (self.y - self.x) * (other.y - other.x);
+        let b = (self.y + self.x) * (other.y + other.x);
+        let c = self.t * D2 * other.t;
+        let zz = self.z * other.z;
+        let d = zz + zz;
+        let (e, f, g, h) = (b - a, d - c, d + c, b + a);
+        Self {
+            x: e * f,
+            y: g * h,
+            z: f * g,
+            t: e * h,
+        }
+    }
+}
+
+// 公開されたスカラー用の倍算。秘密のスカラーには mul_ladder を使う
+impl<U: Bits> Mul<U> for EdwardsPoint {
+    type Output = Self;
+
+    fn mul(self, scalar: U) -> Self {
+        Self::multi_mul(&[(scalar, self)])
+    }
+}
+
+impl Neg for EdwardsPoint {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            t: -self.t,
+            ..self
+        }
+    }
+}
+
+impl ConditionalSwap for EdwardsPoint {
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: bool) {
+        Ed25519Field::conditional_swap(&mut a.x, &mut b.x, choice);
+        Ed25519Field::conditional_swap(&mut a.y, &mut b.y, choice);
+        Ed25519Field::conditional_swap(&mut a.z, &mut b.z, choice);
+        Ed25519Field::conditional_swap(&mut a.t, &mut b.t, choice);
+    }
+}
+
+// リトルエンディアンのバイト列 (32 や 64 バイト) を L で割った余り。
+// 秘密のナンスにも使うので、割り算ではなく 64 ビットずつの Horner 法で畳み込む
+pub fn scalar_from_le_bytes(bytes: &[u8]) -> FieldElement<U256> {
+    let radix = FieldElement::new(U256::one() << 64, L);
+    bytes
+        .rchunks(8)
+        .fold(FieldElement::new(U256::zero(), L), |acc, chunk| {
+            let mut limb = [0u8; 8];
+            limb[..chunk.len()].copy_from_slice(chunk);
+            acc * radix + FieldElement::new(U256::from(u64::from_le_bytes(limb)), L)
+        })
+}
+
+// 2^256 = 38 (mod p) なので、積の上半分を 38 倍して下半分に足す
+fn reduce(product: U512) -> U256 {
+    let (low, high) = product.0.split_at(4);
+    let mut acc = [0u64; 4];
+    let mut carry = 0u128;
+    for i in 0..4 {
+        let wide = low[i] as u128 + 38 * high[i] as u128 + carry;
+        acc[i] = wide as u64;
+        carry = wide >> 64;
+    }
+    // あふれは 1 回目が 39 未満、2 回目は 0 か 1 で、2 回戻せば 0 になる
+    for _ in 0..2 {
+        let mut wide = carry * 38;
+        for a in acc.iter_mut() {
+            wide += *a as u128;
+            *a = wide as u64;
+            wide >>= 64;
+        }
+        carry = wide;
+    }
+    // 2^255 = 19 (mod p) で 2p 未満にしてから 1 回引く
+    let top = acc[3] >> 63;
+    acc[3] &= 0x7FFFFFFFFFFFFFFF;
+    let mut wide = 19 * top as u128;
+    for a in acc.iter_mut() {
+        wide += *a as u128;
+        *a = wide as u64;
+        wide >>= 64;
+    }
+    U256(acc).add_mod(U256::zero(), P)
+}
+
+#[cfg(test)]
+mod edwards25519_tests {
+    use super::*;
+    use crate::field::assert_reduction;
+    use proptest::prelude::*;
+
+    fn hex(s: &str) -> U256 {
+        U256::from_str_radix(s, 16).unwrap()
+    }
+
+    fn bytes(s: &str) -> [u8; 32] {
+        ::hex::decode(s).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(P, (U256::one() << 255) - 19);
+        assert_eq!(
+            L,
+            (U256::one() << 252)
+                + U256::from_dec_str("27742317777372353535851937790883648493").unwrap()
+        );
+        let d = Ed25519Field::new(U256::from(121665), P);
+        assert_eq!(-d / Ed25519Field::new(U256::from(121666), P), D);
+        assert_eq!(D + D, D2);
+
+        let (x, y) = EdwardsPoint::BASE.to_affine();
+        assert_eq!(
+            y * Ed25519Field::new(U256::from(5), P),
+            Ed25519Field::new(U256::from(4), P)
+        );
+        assert!(!x.num.bit(0));
+        assert_eq!(EdwardsPoint::from_affine(x, y), Ok(EdwardsPoint::BASE));
+        assert_eq!(EdwardsPoint::BASE.t, x * y);
+    }
+
+    #[test]
+    fn group() {
+        let b = EdwardsPoint::BASE;
+        assert!((b * L).is_identity());
+        assert!(!b.is_identity());
+        assert_eq!(b + EdwardsPoint::IDENTITY, b);
+        assert_eq!(b + b, b.double());
+        assert_eq!(b + -b, EdwardsPoint::IDENTITY);
+        assert_eq!(b.double() + b, b + b.double());
+        assert_eq!(b.mul_ladder(U256::from(12345), 14), b * U256::from(12345));
+        assert_eq!(
+            EdwardsPoint::multi_mul(&[(U256::from(7), b), (U256::from(5), b.double())]),
+            b * U256::from(17)
+        );
+        assert_eq!(b.mul_by_cofactor(), b * U256::from(8));
+    }
+
+    #[test]
+    fn compress() {
+        let b = EdwardsPoint::BASE;
+        assert_eq!(
+            b.compress(),
+            bytes("5866666666666666666666666666666666666666666666666666666666666666")
+        );
+        let b2 = bytes("c9a3f86aae465f0e56513864510f3997561fa2c9e85ea21dc2292309f3cd6022");
+        assert_eq!(b.double().compress(), b2);
+        assert_eq!(EdwardsPoint::decompress(&b2), Ok(b.double()));
+        assert_eq!(EdwardsPoint::decompress(&b.compress()), Ok(b));
+        assert_eq!(EdwardsPoint::decompress(&(-b).compress()), Ok(-b));
+        assert_eq!(
+            EdwardsPoint::IDENTITY.compress(),
+            bytes("0100000000000000000000000000000000000000000000000000000000000000")
+        );
+    }
+
+    #[test]
+    fn decompress_invalid() {
+        // y >= p
+        let mut y = [0xFFu8; 32];
+        y[0] = 0xED;
+        y[31] = 0x7F;
+        assert_eq!(
+            EdwardsPoint::decompress(&y),
+            Err(CryptoError::InvalidEncoding)
+        );
+        // y = 1 のとき x = 0 なので、符号ビットが立っていてはいけない
+        let mut one = [0u8; 32];
+        one[0] = 1;
+        one[31] = 0x80;
+        assert_eq!(
+            EdwardsPoint::decompress(&one),
+            Err(CryptoError::InvalidEncoding)
+        );
+        // y = 2 に対応する x はない
+        let mut two = [0u8; 32];
+        two[0] = 2;
+        assert_eq!(EdwardsPoint::decompress(&two), Err(CryptoError::NotOnCurve));
+    }
+
+    #[test]
+    fn scalar() {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&{
+            let mut l = [0u8; 32];
+            L.to_little_endian(&mut l);
+            l
+        });
+        assert_eq!(scalar_from_le_bytes(&bytes).num, U256::zero());
+        bytes[0] += 1;
+        assert_eq!(scalar_from_le_bytes(&bytes).num, U256::one());
+
+        let wide = [0xFFu8; 64];
+        let expected = U512::MAX % U512::from(L);
+        assert_eq!(U512::from(scalar_from_le_bytes(&wide).num), expected);
+        assert_eq!(
+            scalar_from_le_bytes(&[0xFF; 32]).num,
+            hex("0FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEC6EF5BF4737DCF70D6EC31748D98951C")
+        );
+    }
+
+    #[test]
+    fn fast_reduction() {
+        let values = [U256::zero(), U256::one(), P - 1, U256::MAX, D.num, L];
+        assert_reduction::<_, Ed25519Reduction>(&values, P);
+    }
+
+    proptest! {
+        #[test]
+        fn fast_reduction_random(a in any::<[u64; 4]>(), b in any::<[u64; 4]>()) {
+            assert_reduction::<_, Ed25519Reduction>(&[U256(a), U256(b)], P);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CryptoError {
+    // num >= prime, or a scalar outside 1..n
+    OutOfRange,
+    // FieldElements with different primes
+    FieldMismatch,
+    // (x, y) does not satisfy y^2 = x^3 + a*x + b
+    NotOnCurve,
+    // Point whose coordinates are not on the curve of its type
+    CurveMismatch,
+    DivisionByZero,
+    // malformed byte encoding (SEC1 etc.)
+    InvalidEncoding,
+    InvalidDer(DerError),
+    // signature from which no public key can be recovered
+    InvalidSignature,
+    // the point at infinity given as a public key
+    InfinityPublicKey,
+}
+
+// Why a DER signature was rejected (strict BIP66 rules)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DerError {
+    // outside 8..=72 bytes
+    InvalidLength,
+    // first byte is not 0x30 (SEQUENCE)
+    NotSequence,
+    // sequence length does not match the input
+    SequenceLengthMismatch,
+    // r or s is not tagged 0x02 (INTEGER)
+    NotInteger,
+    // r or s length runs past the sequence or leaves trailing bytes
+    IntegerLengthMismatch,
+    ZeroLengthInteger,
+    NegativeInteger,
+    // leading 0x00 that is not needed to keep the integer positive
+    ExcessivePadding,
+    // r or s is longer than the curve's field elements
+    IntegerTooLarge,
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CryptoError::OutOfRange => write!(f, "value out of range"),
+            CryptoError::FieldMismatch => write!(f, "field elements are in different fields"),
+            CryptoError::NotOnCurve => write!(f, "point is not on the curve"),
+            CryptoError::CurveMismatch => write!(f, "points are not on the same curve"),
+            CryptoError::DivisionByZero => write!(f, "division by zero"),
+            CryptoError::InvalidEncoding => write!(f, "invalid encoding"),
+            CryptoError::InvalidDer(e) => write!(f, "invalid DER signature: {}", e),
+            CryptoError::InvalidSignature => write!(f, "invalid signature"),
+            CryptoError::InfinityPublicKey => write!(f, "public key is the point at infinity"),
+        }
+    }
+}
+
+impl fmt::Display for DerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DerError::InvalidLength => write!(f, "length must be 8 to 72 bytes"),
+            DerError::NotSequence => write!(f, "missing sequence tag 0x30"),
+            DerError::SequenceLengthMismatch => write!(f, "sequence length mismatch"),
+            DerError::NotInteger => write!(f, "missing integer tag 0x02"),
+            DerError::IntegerLengthMismatch => write!(f, "integer length mismatch"),
+            DerError::ZeroLengthInteger => write!(f, "zero-length integer"),
+            DerError::NegativeInteger => write!(f, "negative integer"),
+            DerError::ExcessivePadding => write!(f, "excessive zero padding"),
+            DerError::IntegerTooLarge => write!(f, "integer longer than the field size"),
+        }
+    }
+}
+
+impl Error for CryptoError {}
@@ -0,0 +1,993 @@
+// T には 0 や 1 を作るトレイトがないので、x - x や v / v で作っている
+#![allow(clippy::eq_op)]
+
+use std::cell::RefCell;
+use std::cmp::{Eq, PartialEq};
+use std::fmt;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::Add;
+use std::ops::Div;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::Rem;
+use std::ops::Shr;
+use std::ops::Sub;
+use std::thread::LocalKey;
+
+use primitive_types::{U256, U512};
+
+use crate::error::CryptoError;
+
+// P-521 の p = 2^521 - 1 は U512 に入らないので、9 語 (576 ビット) の整数を用意する。
+// uint のマクロが展開するコードに clippy が警告を出すので、モジュールに分けて黙らせる
+#[allow(clippy::assign_op_pattern, clippy::manual_div_ceil)]
+mod u576 {
+    uint::construct_uint! {
+        pub struct U576(9);
+    }
+}
+
+pub use u576::U576;
+
+// F_p の元。R は乗算の剰余の取り方で、p が特別な形をしている曲線は
+// 自分のモジュールで Reduction を実装した型を用意し、体の型 Fp<T, R> で選ぶ
+#[derive(Debug)]
+pub struct Fp<T, R>
+where
+    T: Add<Output = T>,
+{
+    pub num: T,
+    pub prime: T,
+    pub reduction: PhantomData<R>,
+}
+
+// R は型の目印にすぎないので、R に Copy を求めない
+impl<T, R> Clone for Fp<T, R>
+where
+    T: Add<Output = T> + Copy,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, R> Copy for Fp<T, R> where T: Add<Output = T> + Copy {}
+
+// 汎用の乗算を使う体。スカラー (位数 n を法とする整数) もこれを使う
+pub type FieldElement<T> = Fp<T, GenericReduction>;
+
+impl<T, R> Fp<T, R>
+where
+    T: PartialOrd + Debug + Add<Output = T>,
+{
+    pub fn new(num: T, prime: T) -> Self {
+        if num >= prime {
+            panic!("Num {:?} not in field range 0 to {:?}", num, prime)
+        }
+        Self {
+            num,
+            prime,
+            reduction: PhantomData,
+        }
+    }
+
+    pub fn try_new(num: T, prime: T) -> Result<Self, CryptoError> {
+        if num >= prime {
+            return Err(CryptoError::OutOfRange);
+        }
+        Ok(Self {
+            num,
+            prime,
+            reduction: PhantomData,
+        })
+    }
+}
+
+// 元が法 prime の体に入っているか。曲線は外から来た座標の p が C::P と同じかをこれで確かめる
+pub trait InField<T> {
+    fn in_field(&self, prime: T) -> bool;
+}
+
+impl<T, R> InField<T> for Fp<T, R>
+where
+    T: PartialOrd + Add<Output = T>,
+{
+    fn in_field(&self, prime: T) -> bool {
+        self.num < self.prime && self.prime == prime
+    }
+}
+
+#[cfg(test)]
+mod field_element_tests {
+    use super::FieldElement;
+    use primitive_types::U256;
+
+    #[test]
+    fn new() {
+        let _ = FieldElement::new(2, 3);
+        let _ = FieldElement::new(U256::from(2), U256::from(3));
+    }
+
+    #[test]
+    fn try_new() {
+        use crate::error::CryptoError;
+
+        assert_eq!(FieldElement::try_new(2, 3), Ok(FieldElement::new(2, 3)));
+        assert_eq!(FieldElement::try_new(3, 3), Err(CryptoError::OutOfRange));
+        assert_eq!(
+            FieldElement::try_new(U256::from(7), U256::from(3)),
+            Err(CryptoError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn in_field() {
+        use super::InField;
+
+        assert!(FieldElement::new(2, 3).in_field(3));
+        assert!(!FieldElement::new(2, 3).in_field(5));
+        // フィールドを直接書いて作った範囲外の元
+        assert!(!FieldElement::<u64> {
+            num: 7,
+            prime: 3,
+            reduction: std::marker::PhantomData,
+        }
+        .in_field(3));
+    }
+}
+
+impl<T, R> fmt::Display for Fp<T, R>
+where
+    T: fmt::Display + Add<Output = T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FieldElement_{}({})", self.prime, self.num)
+    }
+}
+
+impl<T, R> PartialEq for Fp<T, R>
+where
+    T: PartialEq + Add<Output = T>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.prime == other.prime && self.num == other.num
+    }
+}
+
+impl<T, R> Eq for Fp<T, R> where T: Eq + Add<Output = T> {}
+
+#[cfg(test)]
+mod field_element_eq_tests {
+    use super::FieldElement;
+    use primitive_types::U256;
+
+    #[test]
+    fn eq() {
+        let a = FieldElement::new(U256::from(2), U256::from(3));
+        let b = FieldElement::new(U256::from(2), U256::from(3));
+        let c = FieldElement::new(U256::from(1), U256::from(3));
+
+        println!("FieldElement A = {}", a);
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+}
+
+// 法 p での加減乗算。剰余を取る前に T が溢れないようにする。
+// U256, U512, U576 は秘密の値に依存した分岐やメモリアクセスをしない (定数時間) 実装にする
+pub trait Modular: Sized {
+    fn add_mod(self, other: Self, modulus: Self) -> Self;
+    fn sub_mod(self, other: Self, modulus: Self) -> Self;
+    fn mul_mod(self, other: Self, modulus: Self) -> Self;
+}
+
+macro_rules! impl_modular {
+    ($($t:ty => $wide:ty),*) => {
+        $(
+            impl Modular for $t {
+                // p - b を先に計算して、上限付近でも溢れないようにする
+                fn add_mod(self, other: Self, modulus: Self) -> Self {
+                    if self >= modulus - other {
+                        self - (modulus - other)
+                    } else {
+                        self + other
+                    }
+                }
+
+                fn sub_mod(self, other: Self, modulus: Self) -> Self {
+                    if self < other {
+                        modulus - (other - self)
+                    } else {
+                        self - other
+                    }
+                }
+
+                fn mul_mod(self, other: Self, modulus: Self) -> Self {
+                    ((self as $wide * other as $wide) % modulus as $wide) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_modular!(i32 => i64, i64 => i128, u32 => u64, u64 => u128, usize => u128);
+
+// 多倍長整数はモンゴメリ乗算を使う。R = 2^(64 * 語数)
+macro_rules! impl_modular_montgomery {
+    ($($t:ident),*) => {
+        $(
+            impl Modular for $t {
+                fn add_mod(self, other: Self, modulus: Self) -> Self {
+                    let (sum, carry) = self.overflowing_add(other);
+                    let (diff, borrow) = sum.overflowing_sub(modulus);
+                    $t(select(diff.0, sum.0, carry | !borrow))
+                }
+
+                fn sub_mod(self, other: Self, modulus: Self) -> Self {
+                    let (diff, borrow) = self.overflowing_sub(other);
+                    let (wrapped, _) = diff.overflowing_add(modulus);
+                    $t(select(wrapped.0, diff.0, borrow))
+                }
+
+                // mont(mont(a, b), R^2) = a * b mod p。
+                // 偶数の法ではモンゴメリ乗算が使えないので、上位ビットから 2倍と加算を繰り返す
+                fn mul_mod(self, other: Self, modulus: Self) -> Self {
+                    if !modulus.bit(0) {
+                        let a = self % modulus;
+                        let mut ret = $t::zero();
+                        for i in (0..other.bits()).rev() {
+                            ret = ret.add_mod(ret, modulus);
+                            ret = $t(select(ret.add_mod(a, modulus).0, ret.0, other.bit(i)));
+                        }
+                        return ret;
+                    }
+                    let (r2, inv) = montgomery_params(modulus);
+                    let ab = mont_mul(&self.0, &other.0, &modulus.0, inv);
+                    $t(mont_mul(&ab, &r2.0, &modulus.0, inv))
+                }
+            }
+
+            impl MontgomeryParams for $t {
+                fn cache() -> &'static LocalKey<MontgomeryCache<Self>> {
+                    thread_local! {
+                        static CACHE: MontgomeryCache<$t> = RefCell::new(Vec::new());
+                    }
+                    &CACHE
+                }
+
+                fn limbs(&self) -> &[u64] {
+                    &self.0
+                }
+            }
+        )*
+    };
+}
+
+impl_modular_montgomery!(U256, U512, U576);
+
+// (p, R^2 mod p, -p^(-1) mod 2^64)
+type MontgomeryCache<T> = RefCell<Vec<(T, T, u64)>>;
+
+trait MontgomeryParams: Modular + Div<Output = Self> + PartialEq + Copy + 'static {
+    fn cache() -> &'static LocalKey<MontgomeryCache<Self>>;
+    fn limbs(&self) -> &[u64];
+}
+
+// 体の位数と群の位数を行き来するので、いくつかの法を同時に覚えておく
+const MONTGOMERY_CACHE_SIZE: usize = 8;
+
+// 法 p に対する (R^2 mod p, -p^(-1) mod 2^64)。
+// p は公開値なので、割り算を使っても秘密は漏れない
+fn montgomery_params<T: MontgomeryParams>(modulus: T) -> (T, u64) {
+    T::cache().with(|cache| {
+        if let Some(&(_, r2, inv)) = cache.borrow().iter().find(|(p, ..)| *p == modulus) {
+            return (r2, inv);
+        }
+        // 1 を 2 * 64 * 語数 回 2倍して R^2 mod p にする
+        let mut r2 = modulus / modulus;
+        for _ in 0..modulus.limbs().len() * 128 {
+            r2 = r2.add_mod(r2, modulus);
+        }
+        let inv = mont_inv(modulus.limbs()[0]);
+        let mut cache = cache.borrow_mut();
+        if cache.len() == MONTGOMERY_CACHE_SIZE {
+            cache.remove(0);
+        }
+        cache.push((modulus, r2, inv));
+        (r2, inv)
+    })
+}
+
+// choice が真なら a、偽なら b。分岐の代わりにマスクで選ぶ
+fn select<const L: usize>(a: [u64; L], b: [u64; L], choice: bool) -> [u64; L] {
+    let mask = 0u64.wrapping_sub(choice as u64);
+    std::array::from_fn(|i| (a[i] & mask) | (b[i] & !mask))
+}
+
+// -p^(-1) mod 2^64 (ニュートン法)
+fn mont_inv(p0: u64) -> u64 {
+    let mut inv = 1u64;
+    for _ in 0..6 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(p0.wrapping_mul(inv)));
+    }
+    inv.wrapping_neg()
+}
+
+// a * b * R^(-1) mod p (CIOS)。t は L + 2 語で、上の 2 語を t_hi, t_top に分けて持つ
+fn mont_mul<const L: usize>(a: &[u64; L], b: &[u64; L], p: &[u64; L], inv: u64) -> [u64; L] {
+    fn mac(t: u64, a: u64, b: u64, carry: u64) -> (u64, u64) {
+        let wide = t as u128 + a as u128 * b as u128 + carry as u128;
+        (wide as u64, (wide >> 64) as u64)
+    }
+
+    let mut t = [0u64; L];
+    let mut t_hi = 0u64;
+    for bi in b {
+        let mut carry = 0;
+        for (tj, aj) in t.iter_mut().zip(a) {
+            (*tj, carry) = mac(*tj, *aj, *bi, carry);
+        }
+        let (sum, overflow) = t_hi.overflowing_add(carry);
+        let t_top = overflow as u64;
+
+        let m = t[0].wrapping_mul(inv);
+        let (_, mut carry) = mac(t[0], m, p[0], 0);
+        for j in 1..L {
+            (t[j - 1], carry) = mac(t[j], m, p[j], carry);
+        }
+        let (sum, overflow) = sum.overflowing_add(carry);
+        t[L - 1] = sum;
+        t_hi = t_top + overflow as u64;
+    }
+
+    let mut borrow = false;
+    let diff = std::array::from_fn(|i| {
+        let (d, b0) = t[i].overflowing_sub(p[i]);
+        let (d, b1) = d.overflowing_sub(borrow as u64);
+        borrow = b0 | b1;
+        d
+    });
+    select(diff, t, t_hi == 1 || !borrow)
+}
+
+// 乗算 a * b mod p の計算方法。field.rs はどの曲線も知らず、曲線側が体の型引数で選ぶ
+pub trait Reduction<T> {
+    fn mul_mod(a: T, b: T, modulus: T) -> T;
+}
+
+// どの法にも使える Modular::mul_mod (多倍長整数ならモンゴメリ乗算)
+#[derive(Clone, Copy, Debug)]
+pub struct GenericReduction;
+
+impl<T: Modular> Reduction<T> for GenericReduction {
+    fn mul_mod(a: T, b: T, modulus: T) -> T {
+        a.mul_mod(b, modulus)
+    }
+}
+
+// 曲線ごとの Reduction を汎用の乗算と突き合わせる。values のすべての組 (p で割った余り) を試す
+#[cfg(test)]
+pub(crate) fn assert_reduction<T, R>(values: &[T], prime: T)
+where
+    T: Modular + Rem<Output = T> + PartialEq + Debug + Copy,
+    R: Reduction<T>,
+{
+    for &a in values {
+        for &b in values {
+            let (a, b) = (a % prime, b % prime);
+            assert_eq!(
+                R::mul_mod(a, b, prime),
+                GenericReduction::mul_mod(a, b, prime),
+                "{:?} * {:?}",
+                a,
+                b
+            );
+        }
+    }
+}
+
+impl<T, R> Add for Fp<T, R>
+where
+    T: PartialEq + Add<Output = T> + Modular + PartialOrd + Debug + Copy,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        if self.prime != other.prime {
+            panic!("Prime number should be same")
+        }
+        Self::new(self.num.add_mod(other.num, self.prime), self.prime)
+    }
+}
+
+#[cfg(test)]
+mod field_element_add_tests {
+    use super::FieldElement;
+    use primitive_types::U256;
+
+    #[test]
+    fn add() {
+        let a = FieldElement::new(U256::from(2), U256::from(7));
+        let b = FieldElement::new(U256::from(1), U256::from(7));
+        let c = FieldElement::new(U256::from(3), U256::from(7));
+
+        assert_eq!(a + b, c);
+    }
+}
+
+impl<T, R> Sub for Fp<T, R>
+where
+    T: PartialEq + Add<Output = T> + Modular + PartialOrd + Debug + Copy,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        if self.prime != other.prime {
+            panic!("Cannot subtract two numbers in different Fields.");
+        }
+        Self::new(self.num.sub_mod(other.num, self.prime), self.prime)
+    }
+}
+
+impl<T, R> Neg for Fp<T, R>
+where
+    T: PartialEq + Add<Output = T> + Modular + PartialOrd + Debug + Copy,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self - self - self
+    }
+}
+
+#[cfg(test)]
+mod field_element_sub_tests {
+    use super::FieldElement;
+    use primitive_types::U256;
+
+    #[test]
+    fn sub() {
+        let a = FieldElement::new(U256::from(6), U256::from(7));
+        let b = FieldElement::new(U256::from(4), U256::from(7));
+        let c = FieldElement::new(U256::from(2), U256::from(7));
+
+        assert_eq!(a - b, c);
+    }
+
+    #[test]
+    fn neg() {
+        let a = FieldElement::new(U256::from(2), U256::from(7));
+        let zero = FieldElement::new(U256::zero(), U256::from(7));
+
+        assert_eq!(-a, FieldElement::new(U256::from(5), U256::from(7)));
+        assert_eq!(-a + a, zero);
+        assert_eq!(-zero, zero);
+    }
+}
+
+impl<T, R> Mul for Fp<T, R>
+where
+    T: PartialEq + Add<Output = T> + Modular + PartialOrd + Debug + Copy,
+    R: Reduction<T>,
+{
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        if self.prime != other.prime {
+            panic!("Cannot multiply two numbers in different Fields.");
+        }
+        Self::new(R::mul_mod(self.num, other.num, self.prime), self.prime)
+    }
+}
+
+#[cfg(test)]
+mod field_element_mul_tests {
+    use super::FieldElement;
+    use primitive_types::{U256, U512};
+
+    #[test]
+    fn mul() {
+        let a = FieldElement::new(3, 13);
+        let b = FieldElement::new(12, 13);
+        let c = FieldElement::new(10, 13);
+
+        assert_eq!(a * b, c);
+    }
+
+    #[test]
+    fn mul_256bit() {
+        let p = U256::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .unwrap();
+        let minus_one = FieldElement::new(p - 1, p);
+        let one = FieldElement::new(U256::one(), p);
+        let a = FieldElement::new(U256::from(1) << 255, p);
+        let b = FieldElement::new(U256::from(2), p);
+        let c = FieldElement::new(U256::from(0x1000003d1u64), p);
+
+        assert_eq!(minus_one * minus_one, one);
+        assert_eq!(a * b, c);
+    }
+
+    #[test]
+    fn mul_512bit() {
+        // P-384 の p = 2^384 - 2^128 - 2^96 + 2^32 - 1
+        let one = U512::one();
+        let p = (one << 384) - (one << 128) - (one << 96) + (one << 32) - one;
+        let minus_one = FieldElement::new(p - 1, p);
+        let a = FieldElement::new(one << 383, p);
+        let b = FieldElement::new(U512::from(2), p);
+        let c = FieldElement::new((one << 128) + (one << 96) - (one << 32) + one, p);
+
+        assert_eq!(minus_one * minus_one, FieldElement::new(one, p));
+        assert_eq!(a * b, c);
+    }
+
+    #[test]
+    fn mul_even_modulus() {
+        // 偶数の法はモンゴメリ乗算を使わない経路を通る
+        let a = FieldElement::new(U256::from(7), U256::from(10));
+        assert_eq!(a * a, FieldElement::new(U256::from(9), U256::from(10)));
+
+        let p = U512::MAX - 1;
+        let minus_one = FieldElement::new(p - 1, p);
+        assert_eq!(minus_one * minus_one, FieldElement::new(U512::one(), p));
+    }
+}
+
+impl<T, R> Div for Fp<T, R>
+where
+    T: Add<Output = T>
+        + Modular
+        + Sub<Output = T>
+        + Div<Output = T>
+        + Rem<Output = T>
+        + PartialOrd
+        + Debug
+        + Copy,
+    R: Reduction<T>,
+{
+    type Output = Self;
+
+    // a / b = a * b^-1
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.inverse()
+    }
+}
+
+impl<T, R> Fp<T, R>
+where
+    T: Add<Output = T>
+        + Modular
+        + Sub<Output = T>
+        + Div<Output = T>
+        + Rem<Output = T>
+        + PartialOrd
+        + Debug
+        + Copy,
+    R: Reduction<T>,
+{
+    // 繰り返し二乗法: a^e, e は p-1 で割った余りを使う (a^(p-1) = 1)
+    pub fn pow(self, exponent: T) -> Self {
+        let zero = self.prime - self.prime;
+        let one = self.prime / self.prime;
+        let two = one + one;
+        // 0^(p-1) などが 1 にならないよう、0 の累乗は e を簡約する前に 0 と決める (0^0 = 1)
+        if self.num == zero && exponent != zero {
+            return self;
+        }
+        let mut ret = Self::new(one, self.prime);
+        let mut base = self;
+        let mut e = exponent % (self.prime - one);
+        // 符号付きの T では余りが負になりうるので p-1 を足して正にする
+        if e < zero {
+            e = e + (self.prime - one);
+        }
+
+        while e > zero {
+            if e % two == one {
+                ret = ret * base;
+            }
+            base = base * base;
+            e = e / two;
+        }
+        ret
+    }
+
+    // a^(-e) = a^(p-1-e)
+    pub fn pow_neg(self, exponent: T) -> Self {
+        let one = self.prime / self.prime;
+        let order = self.prime - one;
+        self.pow(order - exponent % order)
+    }
+
+    // a^(-1) = a^(p-2)
+    pub fn inverse(self) -> Self {
+        let one = self.prime / self.prime;
+        self.pow(self.prime - one - one)
+    }
+
+    // 演算子版は panic するので、外から来た値にはこちらを使う
+    pub fn checked_add(self, other: Self) -> Result<Self, CryptoError> {
+        self.check_field(&other)?;
+        Ok(self + other)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Result<Self, CryptoError> {
+        self.check_field(&other)?;
+        Ok(self - other)
+    }
+
+    pub fn checked_mul(self, other: Self) -> Result<Self, CryptoError> {
+        self.check_field(&other)?;
+        Ok(self * other)
+    }
+
+    pub fn checked_div(self, other: Self) -> Result<Self, CryptoError> {
+        self.check_field(&other)?;
+        Ok(self * other.checked_inverse()?)
+    }
+
+    pub fn checked_inverse(self) -> Result<Self, CryptoError> {
+        if self.num == self.prime - self.prime {
+            return Err(CryptoError::DivisionByZero);
+        }
+        Ok(self.inverse())
+    }
+
+    fn check_field(&self, other: &Self) -> Result<(), CryptoError> {
+        if self.prime != other.prime {
+            return Err(CryptoError::FieldMismatch);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod field_element_div_tests {
+    use super::FieldElement;
+    use primitive_types::U256;
+
+    #[test]
+    fn pow() {
+        let a = FieldElement::new(U256::from(3), U256::from(13));
+        let b = FieldElement::new(U256::from(1), U256::from(13));
+
+        assert_eq!(a.pow(U256::from(3)), b);
+
+        let a = FieldElement::new(17, 31);
+        assert_eq!(a.pow(3), FieldElement::new(15, 31));
+        assert_eq!(a.pow(0), FieldElement::new(1, 31));
+        assert_eq!(a.pow(30), FieldElement::new(1, 31));
+
+        let zero = FieldElement::new(0u64, 13);
+        assert_eq!(zero.pow(12), zero);
+        assert_eq!(zero.pow(24), zero);
+        assert_eq!(zero.pow(5), zero);
+        assert_eq!(zero.pow(0), FieldElement::new(1, 13));
+    }
+
+    #[test]
+    fn pow_neg() {
+        let a = FieldElement::new(17, 31);
+        assert_eq!(a.pow_neg(3), FieldElement::new(29, 31));
+        assert_eq!(a.pow(-3), FieldElement::new(29, 31));
+
+        let b = FieldElement::new(4, 31);
+        let c = FieldElement::new(11, 31);
+        assert_eq!(b.pow_neg(4) * c, FieldElement::new(13, 31));
+    }
+
+    #[test]
+    fn inverse() {
+        let a = FieldElement::new(U256::from(5), U256::from(19));
+        assert_eq!(
+            a.inverse(),
+            FieldElement::new(U256::from(4), U256::from(19))
+        );
+
+        let p = U256::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .unwrap();
+        let a = FieldElement::new(U256::from(0x1234567890abcdefu64), p);
+        assert_eq!(a * a.inverse(), FieldElement::new(U256::one(), p));
+    }
+
+    #[test]
+    fn div() {
+        let a = FieldElement::new(U256::from(7), U256::from(19));
+        let b = FieldElement::new(U256::from(5), U256::from(19));
+        let c = FieldElement::new(U256::from(9), U256::from(19));
+
+        assert_eq!(a / b, c);
+    }
+
+    #[test]
+    fn checked() {
+        use crate::error::CryptoError;
+
+        let a = FieldElement::new(U256::from(7), U256::from(19));
+        let b = FieldElement::new(U256::from(5), U256::from(19));
+        let zero = FieldElement::new(U256::from(0), U256::from(19));
+        let other = FieldElement::new(U256::from(5), U256::from(13));
+
+        assert_eq!(a.checked_add(b), Ok(a + b));
+        assert_eq!(a.checked_sub(b), Ok(a - b));
+        assert_eq!(a.checked_mul(b), Ok(a * b));
+        assert_eq!(a.checked_div(b), Ok(a / b));
+        assert_eq!(a.checked_add(other), Err(CryptoError::FieldMismatch));
+        assert_eq!(a.checked_sub(other), Err(CryptoError::FieldMismatch));
+        assert_eq!(a.checked_mul(other), Err(CryptoError::FieldMismatch));
+        assert_eq!(a.checked_div(other), Err(CryptoError::FieldMismatch));
+        assert_eq!(a.checked_div(zero), Err(CryptoError::DivisionByZero));
+        assert_eq!(zero.checked_inverse(), Err(CryptoError::DivisionByZero));
+    }
+}
+
+impl<T, R> Fp<T, R>
+where
+    T: Add<Output = T>
+        + Modular
+        + Sub<Output = T>
+        + Div<Output = T>
+        + Rem<Output = T>
+        + PartialOrd
+        + Debug
+        + Copy,
+    R: Reduction<T>,
+{
+    // ルジャンドル記号 a^((p-1)/2): 0 なら 0、平方剰余なら 1、そうでなければ -1
+    pub fn legendre(self) -> i32 {
+        let zero = self.prime - self.prime;
+        let one = self.prime / self.prime;
+        let two = one + one;
+        if self.num == zero {
+            return 0;
+        }
+        if self.prime == two {
+            return 1;
+        }
+        if self.pow((self.prime - one) / two).num == one {
+            1
+        } else {
+            -1
+        }
+    }
+
+    pub fn is_square(self) -> bool {
+        self.legendre() >= 0
+    }
+
+    // p = 3 mod 4 なら a^((p+1)/4)、それ以外は Tonelli-Shanks。平方根が無ければ None
+    pub fn sqrt(self) -> Option<Self> {
+        let zero = self.prime - self.prime;
+        let one = self.prime / self.prime;
+        let two = one + one;
+        let four = two + two;
+        if self.legendre() < 0 {
+            return None;
+        }
+        if self.num == zero || self.prime == two {
+            return Some(self);
+        }
+        if self.prime % four == two + one {
+            // (p+1)/4 = p/4 + 1 (p+1 は T の上限を超えうる)
+            return Some(self.pow(self.prime / four + one));
+        }
+
+        // p - 1 = q * 2^s (q は奇数)
+        let mut q = self.prime - one;
+        let mut s = 0;
+        while q % two == zero {
+            q = q / two;
+            s += 1;
+        }
+        let mut z = Self::new(two, self.prime);
+        while z.legendre() >= 0 {
+            z = z + Self::new(one, self.prime);
+        }
+
+        let mut m = s;
+        let mut c = z.pow(q);
+        let mut t = self.pow(q);
+        let mut r = self.pow((q + one) / two);
+        while t.num != one {
+            // t^(2^i) = 1 となる最小の i (0 < i < m)
+            let mut i = 0;
+            let mut t2i = t;
+            while t2i.num != one {
+                t2i = t2i * t2i;
+                i += 1;
+            }
+            let mut b = c;
+            for _ in 0..m - i - 1 {
+                b = b * b;
+            }
+            m = i;
+            c = b * b;
+            t = t * c;
+            r = r * b;
+        }
+        Some(r)
+    }
+}
+
+#[cfg(test)]
+mod field_element_sqrt_tests {
+    use super::FieldElement;
+    use primitive_types::U256;
+
+    #[test]
+    fn legendre() {
+        assert_eq!(FieldElement::new(0u64, 13).legendre(), 0);
+        assert_eq!(FieldElement::new(4u64, 13).legendre(), 1);
+        assert_eq!(FieldElement::new(2u64, 13).legendre(), -1);
+        assert!(FieldElement::new(10u64, 13).is_square());
+        assert!(!FieldElement::new(5u64, 13).is_square());
+    }
+
+    #[test]
+    fn sqrt_small_primes() {
+        // 3 mod 4 と 1 mod 4 (2^s が大きいもの) を両方含める
+        for p in [2u64, 3, 5, 7, 13, 17, 41, 97, 113, 223, 257, 7681] {
+            for n in 0..p {
+                let a = FieldElement::new(n, p);
+                let exists = (0..p).any(|y| y * y % p == n);
+                match a.sqrt() {
+                    Some(r) => {
+                        assert!(exists);
+                        assert_eq!(r * r, a);
+                    }
+                    None => assert!(!exists),
+                }
+                assert_eq!(a.is_square(), exists);
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_secp256k1() {
+        let p = U256::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .unwrap();
+        let a = FieldElement::new(U256::from(0x1234567890abcdefu64), p);
+        let square = a * a;
+        let r = square.sqrt().unwrap();
+
+        assert!(r == a || r == FieldElement::new(p - a.num, p));
+        // x^3 + 7 (x = 5) は平方剰余ではない
+        let x = FieldElement::new(U256::from(5), p);
+        assert_eq!(
+            (x * x * x + FieldElement::new(U256::from(7), p)).sqrt(),
+            None
+        );
+    }
+
+    #[test]
+    fn sqrt_p_1_mod_4() {
+        // secp256k1 の位数 n は 1 mod 4 なので Tonelli-Shanks を通る
+        let n = U256::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+            16,
+        )
+        .unwrap();
+        let a = FieldElement::new(U256::from(0xdeadbeefu64), n);
+        let r = (a * a).sqrt().unwrap();
+
+        assert_eq!(r * r, a * a);
+    }
+}
+
+// choice が真のときだけ a と b を入れ替える。分岐せずマスクで入れ替える
+pub trait ConditionalSwap {
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: bool);
+}
+
+macro_rules! impl_conditional_swap {
+    ($($t:ident),*) => {
+        $(
+            impl ConditionalSwap for $t {
+                fn conditional_swap(a: &mut Self, b: &mut Self, choice: bool) {
+                    let mask = 0u64.wrapping_sub(choice as u64);
+                    for (x, y) in a.0.iter_mut().zip(b.0.iter_mut()) {
+                        let t = (*x ^ *y) & mask;
+                        *x ^= t;
+                        *y ^= t;
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_conditional_swap!(U256, U512, U576);
+
+impl ConditionalSwap for u64 {
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: bool) {
+        let t = (*a ^ *b) & 0u64.wrapping_sub(choice as u64);
+        *a ^= t;
+        *b ^= t;
+    }
+}
+
+impl<T, R> ConditionalSwap for Fp<T, R>
+where
+    T: Add<Output = T> + ConditionalSwap,
+{
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: bool) {
+        T::conditional_swap(&mut a.num, &mut b.num, choice);
+    }
+}
+
+pub trait Bits {
+    fn bit(&self, index: usize) -> bool;
+    // 最上位の 1 のビット位置 + 1
+    fn bits(&self) -> usize;
+}
+
+macro_rules! impl_bits {
+    ($($t:ident),*) => {
+        $(
+            impl Bits for $t {
+                fn bit(&self, index: usize) -> bool {
+                    $t::bit(self, index)
+                }
+
+                fn bits(&self) -> usize {
+                    $t::bits(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_bits!(U256, U512, U576);
+
+// 曲線の位数や座標に使う多倍長整数。ECDSA を曲線の大きさによらず書くために使う
+pub trait UInt:
+    Modular
+    + Bits
+    + ConditionalSwap
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Shr<usize, Output = Self>
+    + PartialOrd
+    + Debug
+    + Copy
+    + for<'a> From<&'a [u8]>
+{
+    fn is_zero(&self) -> bool;
+    // 上位を 0 で埋めた len バイトのビッグエンディアン
+    fn to_be_bytes(&self, len: usize) -> Vec<u8>;
+}
+
+macro_rules! impl_uint {
+    ($($t:ident),*) => {
+        $(
+            impl UInt for $t {
+                fn is_zero(&self) -> bool {
+                    $t::is_zero(self)
+                }
+
+                fn to_be_bytes(&self, len: usize) -> Vec<u8> {
+                    let mut bytes = [0u8; std::mem::size_of::<$t>()];
+                    self.to_big_endian(&mut bytes);
+                    bytes[bytes.len() - len..].to_vec()
+                }
+            }
+        )*
+    };
+}
+
+impl_uint!(U256, U512, U576);
@@ -0,0 +1,14 @@
+pub mod batch;
+pub mod curve;
+pub mod ecdsa;
+pub mod ed25519;
+pub mod edwards25519;
+pub mod error;
+pub mod field;
+pub mod message;
+pub mod rfc6979;
+pub mod schnorr;
+pub mod secp256k1;
+pub mod secp256r1;
+pub mod secp384r1;
+pub mod secp521r1;
@@ -1,7 +0,0 @@
-// mod ellipticCurve;
-mod fieldElement;
-// mod signature;
-
-fn main() {
-    println!("Hello, world!");
-}
@@ -0,0 +1,189 @@
+use crate::ecdsa::{hash256, recover_public_key, PrivateKey, RecoverableSignature};
+use crate::error::CryptoError;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use primitive_types::U256;
+
+// Bitcoin Core の signmessage / verifymessage 互換
+const MAGIC: &[u8] = b"\x18Bitcoin Signed Message:\n";
+
+// CompactSize でメッセージ長を前に付ける
+fn write_varint(buf: &mut Vec<u8>, n: usize) {
+    match n {
+        0..=0xfc => buf.push(n as u8),
+        0xfd..=0xffff => {
+            buf.push(0xfd);
+            buf.extend_from_slice(&(n as u16).to_le_bytes());
+        }
+        0x10000..=0xffffffff => {
+            buf.push(0xfe);
+            buf.extend_from_slice(&(n as u32).to_le_bytes());
+        }
+        _ => {
+            buf.push(0xff);
+            buf.extend_from_slice(&(n as u64).to_le_bytes());
+        }
+    }
+}
+
+// z = hash256(MAGIC || varint(len) || message)
+pub fn message_hash(message: &[u8]) -> U256 {
+    let mut buf = MAGIC.to_vec();
+    write_varint(&mut buf, message.len());
+    buf.extend_from_slice(message);
+    hash256(&buf)
+}
+
+// 65 バイトのコンパクト署名 header || r || s を base64 にする。
+// header = 27 + recovery_id で、圧縮公開鍵のアドレスなら +4
+pub fn sign_message(key: &PrivateKey, message: &[u8], compressed: bool) -> String {
+    let sig = key.sign_recoverable(message_hash(message));
+    let mut compact = [0u8; 65];
+    compact[0] = 27 + sig.recovery_id + if compressed { 4 } else { 0 };
+    sig.r.to_big_endian(&mut compact[1..33]);
+    sig.s.to_big_endian(&mut compact[33..]);
+    BASE64.encode(compact)
+}
+
+// 署名から公開鍵を復元し、そのアドレスが address と一致するかを返す。
+// 署名やアドレスの形式がおかしいときは Err
+pub fn verify_message(address: &str, signature: &str, message: &[u8]) -> Result<bool, CryptoError> {
+    let compact = BASE64
+        .decode(signature)
+        .map_err(|_| CryptoError::InvalidEncoding)?;
+    if compact.len() != 65 || !(27..=34).contains(&compact[0]) {
+        return Err(CryptoError::InvalidEncoding);
+    }
+    let header = compact[0] - 27;
+    let sig = RecoverableSignature {
+        r: U256::from(&compact[1..33]),
+        s: U256::from(&compact[33..]),
+        recovery_id: header & 3,
+    };
+    let compressed = header & 4 != 0;
+
+    let payload = bs58::decode(address)
+        .with_check(None)
+        .into_vec()
+        .map_err(|_| CryptoError::InvalidEncoding)?;
+    if payload.len() != 21 || !matches!(payload[0], 0x00 | 0x6f) {
+        return Err(CryptoError::InvalidEncoding);
+    }
+
+    let point = recover_public_key(message_hash(message), &sig)?;
+    Ok(point.hash160(compressed)[..] == payload[1..])
+}
+
+#[cfg(test)]
+mod message_tests {
+    use super::*;
+
+    fn key(secret: &str) -> PrivateKey {
+        PrivateKey::new(U256::from_str_radix(secret, 16).unwrap())
+    }
+
+    #[test]
+    fn varint() {
+        let cases: [(usize, &[u8]); 5] = [
+            (0, &[0]),
+            (0xfc, &[0xfc]),
+            (0xfd, &[0xfd, 0xfd, 0]),
+            (0x1234, &[0xfd, 0x34, 0x12]),
+            (0x10000, &[0xfe, 0, 0, 1, 0]),
+        ];
+        for (n, expected) in cases {
+            let mut buf = vec![];
+            write_varint(&mut buf, n);
+            assert_eq!(buf, expected);
+        }
+    }
+
+    #[test]
+    fn sign() {
+        // bitcoinjs-message の例 (WIF 5KYZdUEo39z3FPrtuX2QbbwGnNP5zTd7yyr2SC1j299sBCnWjss)
+        let key = key("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        let message = b"This is an example of a signed message.";
+
+        let sig = sign_message(&key, message, false);
+        assert_eq!(
+            sig,
+            "G9L5yLFjti0QTHhPyFrZCT1V/MMnBtXKmoiKDZ78NDBjERki6ZTQZdSMCtkgoNmp17By9ItJr8o7ChX0XxY91nk="
+        );
+        assert_eq!(
+            verify_message("1HZwkjkeaoZfTSaJxDw6aKkxp45agDiEzN", &sig, message),
+            Ok(true)
+        );
+
+        let sig = sign_message(&key, message, true);
+        assert_eq!(
+            sig,
+            "H9L5yLFjti0QTHhPyFrZCT1V/MMnBtXKmoiKDZ78NDBjERki6ZTQZdSMCtkgoNmp17By9ItJr8o7ChX0XxY91nk="
+        );
+        assert_eq!(
+            verify_message("1F3sAm6ZtwLAUnj7d38pGFxtP3RVEvtsbV", &sig, message),
+            Ok(true)
+        );
+        // 圧縮フラグが違えばアドレスも違う
+        assert_eq!(
+            verify_message("1HZwkjkeaoZfTSaJxDw6aKkxp45agDiEzN", &sig, message),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn verify() {
+        let key = key("1");
+        let address = key.public_key.point().address(true, false);
+        assert_eq!(address, "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+
+        let sig = "H+0Hz9TQ827HsHUaT+4G7FBJ6ssQOzZoSE2T32jxUmnjUu9NzFVynL9v1C++nr4IwhT5KX3iJcNRGgjAjdqKvIs=";
+        assert_eq!(sign_message(&key, b"Hello, world!", true), sig);
+        assert_eq!(verify_message(&address, sig, b"Hello, world!"), Ok(true));
+        assert_eq!(verify_message(&address, sig, b"Hello, world?"), Ok(false));
+
+        // testnet アドレスでも検証できる
+        let testnet = key.public_key.point().address(true, true);
+        assert_eq!(verify_message(&testnet, sig, b"Hello, world!"), Ok(true));
+
+        // 253 バイト以上のメッセージは 3 バイトの長さを付ける
+        let long = [b'x'; 300];
+        let sig = sign_message(&key, &long, true);
+        assert_eq!(verify_message(&address, &sig, &long), Ok(true));
+    }
+
+    #[test]
+    fn verify_invalid() {
+        let key = key("3039");
+        let address = "1Fy668EHkFwsrBQJfZsXYVgsGzKDaZhUEj";
+        let sig = "HGcnqI99Q4sdvTojhblDJ82o2opQ2LPr/2lqt8uGtTOmWdBqGh750Wk6YI+ZARtqr5EysP7eb+30Ml09/oaYvcE=";
+        assert_eq!(sign_message(&key, b"", false), sig);
+        assert_eq!(verify_message(address, sig, b""), Ok(true));
+
+        let mut compact = BASE64.decode(sig).unwrap();
+        assert_eq!(
+            verify_message(address, &BASE64.encode(&compact[..64]), b""),
+            Err(CryptoError::InvalidEncoding)
+        );
+        compact[0] = 35;
+        assert_eq!(
+            verify_message(address, &BASE64.encode(&compact), b""),
+            Err(CryptoError::InvalidEncoding)
+        );
+        assert_eq!(
+            verify_message(address, "not base64!", b""),
+            Err(CryptoError::InvalidEncoding)
+        );
+        // チェックサムが合わないアドレス
+        assert_eq!(
+            verify_message("1Fy668EHkFwsrBQJfZsXYVgsGzKDaZhUEk", sig, b""),
+            Err(CryptoError::InvalidEncoding)
+        );
+        // r = 0 の署名からは公開鍵を復元できない
+        let mut zero_r = BASE64.decode(sig).unwrap();
+        zero_r[1..33].fill(0);
+        assert_eq!(
+            verify_message(address, &BASE64.encode(zero_r), b""),
+            Err(CryptoError::OutOfRange)
+        );
+    }
+}
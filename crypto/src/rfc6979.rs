@@ -0,0 +1,198 @@
+use std::marker::PhantomData;
+
+use crate::field::UInt;
+use primitive_types::U256;
+use sha2::digest::core_api::BlockSizeUser;
+use sha2::{Digest, Sha256};
+
+// HMAC (RFC 2104)。data はつなげてから MAC を取る
+pub fn hmac<D: Digest + BlockSizeUser>(key: &[u8], data: &[&[u8]]) -> Vec<u8> {
+    let mut block = vec![0u8; D::block_size()];
+    if key.len() > block.len() {
+        let digest = D::digest(key);
+        block[..digest.len()].copy_from_slice(&digest);
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = D::new();
+    inner.update(block.iter().map(|b| b ^ 0x36).collect::<Vec<_>>());
+    for d in data {
+        inner.update(d);
+    }
+    let mut outer = D::new();
+    outer.update(block.iter().map(|b| b ^ 0x5c).collect::<Vec<_>>());
+    outer.update(inner.finalize());
+    outer.finalize().to_vec()
+}
+
+pub fn hmac_sha256(key: &[u8], data: &[&[u8]]) -> [u8; 32] {
+    hmac::<Sha256>(key, data).try_into().unwrap()
+}
+
+// RFC 6979 の決定的な k の候補を順に返す。
+// 候補から作った署名が r = 0 や s = 0 になったら次の候補を使う
+pub struct Rfc6979<T = U256, D = Sha256> {
+    k: Vec<u8>,
+    v: Vec<u8>,
+    order: T,
+    digest: PhantomData<D>,
+}
+
+impl Rfc6979 {
+    // secp256k1 と P-256 (SHA-256) 用
+    pub fn new(secret: U256, z: U256, order: U256) -> Self {
+        Self::with_digest(secret, z, order)
+    }
+}
+
+impl<T: UInt, D: Digest + BlockSizeUser> Rfc6979<T, D> {
+    // z は bits2int 済み (n のビット長に切り詰めた) ハッシュ値
+    pub fn with_digest(secret: T, z: T, order: T) -> Self {
+        // bits2octets(h1) = int2octets(h1 mod q)
+        let z = if z >= order { z - order } else { z };
+        let len = Self::rlen(order);
+        let x = secret.to_be_bytes(len);
+        let h = z.to_be_bytes(len);
+
+        let v = vec![0x01u8; <D as Digest>::output_size()];
+        let k = vec![0x00u8; v.len()];
+        let k = hmac::<D>(&k, &[&v, &[0x00], &x, &h]);
+        let v = hmac::<D>(&k, &[&v]);
+        let k = hmac::<D>(&k, &[&v, &[0x01], &x, &h]);
+        let v = hmac::<D>(&k, &[&v]);
+
+        Self {
+            k,
+            v,
+            order,
+            digest: PhantomData,
+        }
+    }
+
+    // rlen = ceil(qlen / 8)
+    fn rlen(order: T) -> usize {
+        order.bits().div_ceil(8)
+    }
+}
+
+impl<T: UInt, D: Digest + BlockSizeUser> Iterator for Rfc6979<T, D> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let len = Self::rlen(self.order);
+        loop {
+            // qlen ビットに達するまで V をつなげ、先頭の qlen ビットを候補にする
+            let mut t = vec![];
+            while t.len() < len {
+                self.v = hmac::<D>(&self.k, &[&self.v]);
+                t.extend_from_slice(&self.v);
+            }
+            let k = T::from(&t[..len]) >> (len * 8 - self.order.bits());
+            // 次の候補のために K, V を進めておく
+            self.k = hmac::<D>(&self.k, &[&self.v, &[0x00]]);
+            self.v = hmac::<D>(&self.k, &[&self.v]);
+            if !k.is_zero() && k < self.order {
+                return Some(k);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod rfc6979_tests {
+    use super::*;
+    use crate::field::U576;
+    use primitive_types::U512;
+    use sha2::{Sha384, Sha512};
+
+    fn hex(s: &str) -> U256 {
+        U256::from_str_radix(s, 16).unwrap()
+    }
+
+    fn sha256(message: &[u8]) -> U256 {
+        U256::from(&Sha256::digest(message)[..])
+    }
+
+    #[test]
+    fn hmac() {
+        // RFC 4231 test case 2
+        let mac = hmac_sha256(b"Jefe", &[b"what do ya want ", b"for nothing?"]);
+        assert_eq!(
+            ::hex::encode(mac),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // ブロック長 128 バイトの SHA-512
+        let mac = super::hmac::<Sha512>(b"Jefe", &[b"what do ya want for nothing?"]);
+        assert_eq!(
+            ::hex::encode(mac),
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+        );
+    }
+
+    #[test]
+    fn p256_vectors() {
+        // RFC 6979 A.2.5 (P-256, SHA-256)
+        let q = hex("FFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551");
+        let x = hex("C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721");
+
+        let mut k = Rfc6979::new(x, sha256(b"sample"), q);
+        assert_eq!(
+            k.next(),
+            Some(hex(
+                "A6E3C57DD01ABE90086538398355DD4C3B17AA873382B0F24D6129493D8AAD60"
+            ))
+        );
+        let mut k = Rfc6979::new(x, sha256(b"test"), q);
+        assert_eq!(
+            k.next(),
+            Some(hex(
+                "D16B6AE827F17175E040871A1C7EC3500192C4C92677336EC2537ACAEE0008E0"
+            ))
+        );
+    }
+
+    #[test]
+    fn secp256k1_vector() {
+        let n = hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141");
+        let mut k = Rfc6979::new(U256::one(), sha256(b"Satoshi Nakamoto"), n);
+        assert_eq!(
+            k.next(),
+            Some(hex(
+                "8F8A276C19F4149656B280621E358CCE24F5F52542772691EE69063B74F15D15"
+            ))
+        );
+        // 2 つ目以降の候補も決定的で、1 つ目とは異なる
+        let second = k.next().unwrap();
+        let mut again = Rfc6979::new(U256::one(), sha256(b"Satoshi Nakamoto"), n);
+        again.next();
+        assert_eq!(again.next(), Some(second));
+        assert_ne!(
+            second,
+            hex("8F8A276C19F4149656B280621E358CCE24F5F52542772691EE69063B74F15D15")
+        );
+    }
+
+    #[test]
+    fn p384_p521_vectors() {
+        // RFC 6979 A.2.6 (P-384, SHA-384)
+        let q = crate::secp384r1::N;
+        let x = U512::from_str_radix("6B9D3DAD2E1B8C1C05B19875B6659F4DE23C3B667BF297BA9AA47740787137D896D5724E4C70A825F872C9EA60D2EDF5", 16).unwrap();
+        let z = U512::from(&Sha384::digest(b"sample")[..]);
+        let mut k = Rfc6979::<U512, Sha384>::with_digest(x, z, q);
+        assert_eq!(
+            k.next(),
+            U512::from_str_radix("94ED910D1A099DAD3254E9242AE85ABDE4BA15168EAF0CA87A555FD56D10FBCA2907E3E83BA95368623B8C4686915CF9", 16).ok()
+        );
+
+        // RFC 6979 A.2.7 (P-521, SHA-512)。qlen = 521 なので V を 2 回つなげて先頭 521 ビットを使う
+        let q = crate::secp521r1::N;
+        let x = U576::from_str_radix("0FAD06DAA62BA3B25D2FB40133DA757205DE67F5BB0018FEE8C86E1B68C7E75CAA896EB32F1F47C70855836A6D16FCC1466F6D8FBEC67DB89EC0C08B0E996B83538", 16).unwrap();
+        let z = U576::from(&Sha512::digest(b"sample")[..]);
+        let mut k = Rfc6979::<U576, Sha512>::with_digest(x, z, q);
+        assert_eq!(
+            k.next(),
+            U576::from_str_radix("1DAE2EA071F8110DC26882D4D5EAE0621A3256FC8847FB9022E2B7D28E6F10198B1574FDD03A9053C08A1854A168AA5A57470EC97DD5CE090124EF52A2F7ECBFFD3", 16).ok()
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use crate::curve::Point;
+use crate::ecdsa::scalar;
+use crate::error::CryptoError;
+use crate::secp256k1::{to_bytes, S256Point, G, N, P};
+use primitive_types::U256;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+// BIP340: tagged_hash(tag, x) = SHA256(SHA256(tag) || SHA256(tag) || x)
+pub fn tagged_hash(tag: &str, data: &[&[u8]]) -> [u8; 32] {
+    let tag = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(tag);
+    hasher.update(tag);
+    for d in data {
+        hasher.update(d);
+    }
+    hasher.finalize().into()
+}
+
+// 公開鍵は x 座標だけ (y は偶数の方とみなす)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct XOnlyPublicKey(pub U256);
+
+impl XOnlyPublicKey {
+    pub fn from_secret(secret: U256) -> Result<Self, CryptoError> {
+        if secret.is_zero() || secret >= N {
+            return Err(CryptoError::OutOfRange);
+        }
+        Ok(Self(x_of(S256Point::mul_base(secret))))
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        to_bytes(self.0)
+    }
+}
+
+// R.x || s の 64 バイト
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SchnorrSignature {
+    pub r: U256,
+    pub s: U256,
+}
+
+impl fmt::Display for SchnorrSignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SchnorrSignature({:x}, {:x})", self.r, self.s)
+    }
+}
+
+impl SchnorrSignature {
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut ret = [0u8; 64];
+        ret[..32].copy_from_slice(&to_bytes(self.r));
+        ret[32..].copy_from_slice(&to_bytes(self.s));
+        ret
+    }
+
+    // 範囲のチェックは検証時に行う
+    pub fn from_bytes(bytes: &[u8; 64]) -> Self {
+        Self {
+            r: U256::from(&bytes[..32]),
+            s: U256::from(&bytes[32..]),
+        }
+    }
+}
+
+fn x_of(point: S256Point) -> U256 {
+    match point.0 {
+        Point::Coordinate { x, .. } => x.num,
+        Point::Infinity => unreachable!("secret scalar multiple is never infinity"),
+    }
+}
+
+fn has_even_y(point: S256Point) -> bool {
+    match point.0 {
+        Point::Coordinate { y, .. } => !y.num.bit(0),
+        Point::Infinity => false,
+    }
+}
+
+// e = int(hash_challenge(R.x || P.x || m)) mod n
+pub fn challenge(r: U256, public_key: U256, message: &[u8]) -> U256 {
+    let e = tagged_hash(
+        "BIP0340/challenge",
+        &[&to_bytes(r), &to_bytes(public_key), message],
+    );
+    U256::from(&e[..]) % N
+}
+
+pub fn schnorr_sign(
+    secret: U256,
+    message: &[u8],
+    aux_rand: &[u8; 32],
+) -> Result<SchnorrSignature, CryptoError> {
+    if secret.is_zero() || secret >= N {
+        return Err(CryptoError::OutOfRange);
+    }
+    // P.y が奇数なら d = n - d' として、P を偶数 y の点に揃える
+    let point = S256Point::mul_base(secret);
+    let d = if has_even_y(point) {
+        scalar(secret)
+    } else {
+        -scalar(secret)
+    };
+    let public_key = x_of(point);
+
+    let mut t = tagged_hash("BIP0340/aux", &[aux_rand]);
+    for (t, d) in t.iter_mut().zip(to_bytes(d.num)) {
+        *t ^= d;
+    }
+    let rand = tagged_hash("BIP0340/nonce", &[&t, &to_bytes(public_key), message]);
+    let k = scalar(U256::from(&rand[..]));
+    if k.num.is_zero() {
+        return Err(CryptoError::InvalidSignature);
+    }
+
+    let r = S256Point::mul_base(k.num);
+    let k = if has_even_y(r) { k } else { -k };
+    let r = x_of(r);
+    let e = scalar(challenge(r, public_key, message));
+
+    Ok(SchnorrSignature {
+        r,
+        s: (k + e * d).num,
+    })
+}
+
+// R = s*G - e*P が偶数 y の点で、R.x = r になるか
+pub fn schnorr_verify(public_key: &XOnlyPublicKey, message: &[u8], sig: &SchnorrSignature) -> bool {
+    let point = match S256Point::lift_x(public_key.0) {
+        Ok(point) => point,
+        Err(_) => return false,
+    };
+    if sig.r >= P || sig.s >= N {
+        return false;
+    }
+    let e = scalar(challenge(sig.r, public_key.0, message));
+    let total = S256Point::multi_mul(&[(sig.s, G), ((-e).num, point)]);
+
+    match total.0 {
+        Point::Coordinate { x, .. } => has_even_y(total) && x.num == sig.r,
+        Point::Infinity => false,
+    }
+}
+
+#[cfg(test)]
+mod schnorr_tests {
+    use super::*;
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv
+    const BIP340_VECTORS: &str = "\
+index,secret key,public key,aux_rand,message,signature,verification result,comment
+0,0000000000000000000000000000000000000000000000000000000000000003,F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9,0000000000000000000000000000000000000000000000000000000000000000,0000000000000000000000000000000000000000000000000000000000000000,E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0,TRUE,
+1,B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,0000000000000000000000000000000000000000000000000000000000000001,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A,TRUE,
+2,C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9,DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8,C87AA53824B4D7AE2EB035A2B5BBBCCC080E76CDC6D1692C4B0B62D798E6D906,7E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C,5831AAEED7B44BB74E5EAB94BA9D4294C49BCF2A60728D8B4C200F50DD313C1BAB745879A5AD954A72C45A91C3A51D3C7ADEA98D82F8481E0E1E03674A6F3FB7,TRUE,
+3,0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710,25D1DFF95105F5253C4022F628A996AD3A0D95FBF21D468A1B33F8C160D8F517,FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF,FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF,7EB0509757E246F19449885651611CB965ECC1A187DD51B64FDA1EDC9637D5EC97582B9CB13DB3933705B32BA982AF5AF25FD78881EBB32771FC5922EFC66EA3,TRUE,test fails if msg is reduced modulo p or n
+4,,D69C3509BB99E412E68B0FE8544E72837DFA30746D8BE2AA65975F29D22DC7B9,,4DF3C3F68FCC83B27E9D42C90431A72499F17875C81A599B566C9889B9696703,00000000000000000000003B78CE563F89A0ED9414F5AA28AD0D96D6795F9C6376AFB1548AF603B3EB45C9F8207DEE1060CB71C04E80F593060B07D28308D7F4,TRUE,
+5,,EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B,FALSE,public key not on the curve
+6,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,FFF97BD5755EEEA420453A14355235D382F6472F8568A18B2F057A14602975563CC27944640AC607CD107AE10923D9EF7A73C643E166BE5EBEAFA34B1AC553E2,FALSE,has_even_y(R) is false
+7,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,1FA62E331EDBC21C394792D2AB1100A7B432B013DF3F6FF4F99FCB33E0E1515F28890B3EDB6E7189B630448B515CE4F8622A954CFE545735AAEA5134FCCDB2BD,FALSE,negated message
+8,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769961764B3AA9B2FFCB6EF947B6887A226E8D7C93E00C5ED0C1834FF0D0C2E6DA6,FALSE,negated s value
+9,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,0000000000000000000000000000000000000000000000000000000000000000123DDA8328AF9C23A94C1FEECFD123BA4FB73476F0D594DCB65C6425BD186051,FALSE,sG - eP is infinite. Test fails in single verification if has_even_y(inf) is defined as true and x(inf) as 0
+10,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,00000000000000000000000000000000000000000000000000000000000000017615FBAF5AE28864013C099742DEADB4DBA87F11AC6754F93780D5A1837CF197,FALSE,sG - eP is infinite. Test fails in single verification if has_even_y(inf) is defined as true and x(inf) as 1
+11,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,4A298DACAE57395A15D0795DDBFD1DCB564DA82B0F269BC70A74F8220429BA1D69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B,FALSE,sig[0:32] is not an X coordinate on the curve
+12,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B,FALSE,sig[0:32] is equal to field size
+13,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141,FALSE,sig[32:64] is equal to curve order
+14,,FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B,FALSE,public key is not a valid X coordinate because it exceeds the field size
+15,0340034003400340034003400340034003400340034003400340034003400340,778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117,0000000000000000000000000000000000000000000000000000000000000000,,71535DB165ECD9FBBC046E5FFAEA61186BB6AD436732FCCC25291A55895464CF6069CE26BF03466228F19A3A62DB8A649F2D560FAC652827D1AF0574E427AB63,TRUE,message of size 0 (added 2022-12)
+16,0340034003400340034003400340034003400340034003400340034003400340,778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117,0000000000000000000000000000000000000000000000000000000000000000,11,08A20A0AFEF64124649232E0693C583AB1B9934AE63B4C3511F3AE1134C6A303EA3173BFEA6683BD101FA5AA5DBC1996FE7CACFC5A577D33EC14564CEC2BACBF,TRUE,message of size 1 (added 2022-12)
+17,0340034003400340034003400340034003400340034003400340034003400340,778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117,0000000000000000000000000000000000000000000000000000000000000000,0102030405060708090A0B0C0D0E0F1011,5130F39A4059B43BC7CAC09A19ECE52B5D8699D1A71E3C52DA9AFDB6B50AC370C4A482B77BF960F8681540E25B6771ECE1E5A37FD80E5A51897C5566A97EA5A5,TRUE,message of size 17 (added 2022-12)
+18,0340034003400340034003400340034003400340034003400340034003400340,778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117,0000000000000000000000000000000000000000000000000000000000000000,99999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999,403B12B0D8555A344175EA7EC746566303321E5DBFA8BE6F091635163ECA79A8585ED3E3170807E7C03B720FC54C7B23897FCBA0E9D0B4A06894CFD249F22367,TRUE,message of size 100 (added 2022-12)
+";
+
+    fn hex(s: &str) -> U256 {
+        U256::from_str_radix(s, 16).unwrap()
+    }
+
+    #[test]
+    fn bip340_vectors() {
+        for line in BIP340_VECTORS.lines().skip(1) {
+            let fields: Vec<&str> = line.splitn(8, ',').collect();
+            let (index, secret, public_key, aux_rand, message, signature, result) = (
+                fields[0], fields[1], fields[2], fields[3], fields[4], fields[5], fields[6],
+            );
+            let public_key = XOnlyPublicKey(hex(public_key));
+            let message = ::hex::decode(message).unwrap();
+            let signature: [u8; 64] = ::hex::decode(signature).unwrap().try_into().unwrap();
+            let signature = SchnorrSignature::from_bytes(&signature);
+
+            if !secret.is_empty() {
+                let secret = hex(secret);
+                let aux_rand: [u8; 32] = ::hex::decode(aux_rand).unwrap().try_into().unwrap();
+                assert_eq!(
+                    XOnlyPublicKey::from_secret(secret),
+                    Ok(public_key),
+                    "public key for index {}",
+                    index
+                );
+                assert_eq!(
+                    schnorr_sign(secret, &message, &aux_rand),
+                    Ok(signature),
+                    "signature for index {}",
+                    index
+                );
+            }
+            assert_eq!(
+                schnorr_verify(&public_key, &message, &signature),
+                result == "TRUE",
+                "verification for index {}",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn tagged_hash_prefix() {
+        // タグのハッシュを 2 回前に付けるので、データを分けて渡しても同じ
+        assert_eq!(
+            tagged_hash("BIP0340/challenge", &[b"ab", b"c"]),
+            tagged_hash("BIP0340/challenge", &[b"abc"])
+        );
+        assert_ne!(
+            tagged_hash("BIP0340/challenge", &[b"abc"]),
+            tagged_hash("BIP0340/aux", &[b"abc"])
+        );
+    }
+
+    #[test]
+    fn sign_invalid() {
+        let aux_rand = [0u8; 32];
+        assert_eq!(
+            schnorr_sign(U256::zero(), b"", &aux_rand),
+            Err(CryptoError::OutOfRange)
+        );
+        assert_eq!(
+            schnorr_sign(N, b"", &aux_rand),
+            Err(CryptoError::OutOfRange)
+        );
+        assert_eq!(XOnlyPublicKey::from_secret(N), Err(CryptoError::OutOfRange));
+    }
+
+    #[test]
+    fn sign_verify() {
+        // 奇数 y の公開鍵を持つ秘密鍵でも、x だけで検証できる
+        for secret in [1u64, 2, 3, 12345, 0xdeadbeef] {
+            let secret = U256::from(secret);
+            let public_key = XOnlyPublicKey::from_secret(secret).unwrap();
+            let sig = schnorr_sign(secret, b"message", &[7u8; 32]).unwrap();
+            assert!(schnorr_verify(&public_key, b"message", &sig));
+            assert!(!schnorr_verify(&public_key, b"massage", &sig));
+            assert_eq!(SchnorrSignature::from_bytes(&sig.to_bytes()), sig);
+        }
+    }
+}
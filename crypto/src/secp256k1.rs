@@ -0,0 +1,453 @@
+use crate::curve::{Curve, FixedBaseTable, Point};
+use crate::error::CryptoError;
+use crate::field::FieldElement;
+use primitive_types::U256;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Add;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::sync::OnceLock;
+
+// secp256k1: y^2 = x^3 + 7 over F_p, limbs are little-endian u64 words
+pub const P: U256 = U256([
+    0xFFFFFFFEFFFFFC2F,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+]);
+
+// Order of G
+pub const N: U256 = U256([
+    0xBFD25E8CD0364141,
+    0xBAAEDCE6AF48A03B,
+    0xFFFFFFFFFFFFFFFE,
+    0xFFFFFFFFFFFFFFFF,
+]);
+
+pub const GX: U256 = U256([
+    0x59F2815B16F81798,
+    0x029BFCDB2DCE28D9,
+    0x55A06295CE870B07,
+    0x79BE667EF9DCBBAC,
+]);
+
+pub const GY: U256 = U256([
+    0x9C47D08FFB10D4B8,
+    0xFD17B448A6855419,
+    0x5DA4FBFC0E1108A8,
+    0x483ADA7726A3C465,
+]);
+
+pub type S256Field = FieldElement<U256>;
+
+pub const A: S256Field = FieldElement {
+    num: U256([0; 4]),
+    prime: P,
+    reduction: PhantomData,
+};
+
+pub const B: S256Field = FieldElement {
+    num: U256([7, 0, 0, 0]),
+    prime: P,
+    reduction: PhantomData,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Secp256k1;
+
+impl Curve for Secp256k1 {
+    type Field = S256Field;
+    type Int = U256;
+
+    const A: S256Field = A;
+    const B: S256Field = B;
+    const ONE: S256Field = FieldElement {
+        num: U256([1, 0, 0, 0]),
+        prime: P,
+        reduction: PhantomData,
+    };
+    const P: U256 = P;
+    const N: U256 = N;
+    const H: U256 = U256([1, 0, 0, 0]);
+    const G: Point<Self> = Point::Coordinate {
+        x: FieldElement {
+            num: GX,
+            prime: P,
+            reduction: PhantomData,
+        },
+        y: FieldElement {
+            num: GY,
+            prime: P,
+            reduction: PhantomData,
+        },
+    };
+}
+
+pub const G: S256Point = S256Point(Secp256k1::G);
+
+// G の倍数表は最初に使うときに一度だけ作る
+static G_TABLE: OnceLock<FixedBaseTable<Secp256k1>> = OnceLock::new();
+
+fn g_table() -> &'static FixedBaseTable<Secp256k1> {
+    G_TABLE.get_or_init(|| FixedBaseTable::new(G.0, 256))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct S256Point(pub Point<Secp256k1>);
+
+impl S256Point {
+    pub fn new(x: U256, y: U256) -> Self {
+        Self(Point::new(FieldElement::new(x, P), FieldElement::new(y, P)))
+    }
+
+    pub fn try_new(x: U256, y: U256) -> Result<Self, CryptoError> {
+        Ok(Self(Point::try_new(
+            FieldElement::try_new(x, P)?,
+            FieldElement::try_new(y, P)?,
+        )?))
+    }
+
+    pub fn infinity() -> Self {
+        Self(Point::Infinity)
+    }
+
+    // SEC1: 非圧縮は 04 || x || y、圧縮は (y が偶数なら 02、奇数なら 03) || x
+    pub fn sec(&self, compressed: bool) -> Vec<u8> {
+        match self.0 {
+            Point::Coordinate { x, y, .. } => {
+                let mut ret = Vec::with_capacity(65);
+                if compressed {
+                    ret.push(if y.num.bit(0) { 3 } else { 2 });
+                    ret.extend_from_slice(&to_bytes(x.num));
+                } else {
+                    ret.push(4);
+                    ret.extend_from_slice(&to_bytes(x.num));
+                    ret.extend_from_slice(&to_bytes(y.num));
+                }
+                ret
+            }
+            Point::Infinity => vec![0],
+        }
+    }
+
+    pub fn parse_sec(sec: &[u8]) -> Result<Self, CryptoError> {
+        match (sec.first(), sec.len()) {
+            // 00 は無限遠点だが、公開鍵としては使えないので読み込まない
+            (Some(0), 1) => Err(CryptoError::InfinityPublicKey),
+            (Some(4), 65) => Self::try_new(U256::from(&sec[1..33]), U256::from(&sec[33..])),
+            (Some(2 | 3), 33) => {
+                let point = Self::lift_x(U256::from(&sec[1..]))?;
+                Ok(if sec[0] == 3 { -point } else { point })
+            }
+            _ => Err(CryptoError::InvalidEncoding),
+        }
+    }
+
+    // x 座標から y が偶数の方の点を復元する (BIP340 の lift_x)
+    pub fn lift_x(x: U256) -> Result<Self, CryptoError> {
+        let x = FieldElement::try_new(x, P)?;
+        let alpha = x * x * x + B;
+        let beta = alpha.sqrt().ok_or(CryptoError::NotOnCurve)?;
+        let y = if beta.num.bit(0) { A - beta } else { beta };
+        Ok(Self(Point::Coordinate { x, y }))
+    }
+
+    // hash160 = RIPEMD160(SHA256(sec))
+    pub fn hash160(&self, compressed: bool) -> [u8; 20] {
+        Ripemd160::digest(Sha256::digest(self.sec(compressed))).into()
+    }
+
+    // P2PKH アドレス: Base58Check(version || hash160)、version は mainnet 0x00, testnet 0x6f
+    pub fn address(&self, compressed: bool, testnet: bool) -> String {
+        let version = if testnet { 0x6f } else { 0x00 };
+        bs58::encode(self.hash160(compressed))
+            .with_check_version(version)
+            .into_string()
+    }
+
+    // k1*P1 + k2*P2 + ... (検証用。スカラーは公開された値であること)
+    pub fn multi_mul(terms: &[(U256, S256Point)]) -> Self {
+        let terms: Vec<_> = terms.iter().map(|(k, p)| (*k % N, p.0)).collect();
+        Self(Point::multi_mul(&terms))
+    }
+
+    // k*G を倍数表で計算する。鍵生成や署名のノンスなど秘密のスカラーにも使える
+    pub fn mul_base(k: U256) -> Self {
+        Self(g_table().mul(k))
+    }
+
+    // 秘密鍵やノンス k (0 < k < n) 用の定数時間スカラー倍
+    pub fn mul_secret(self, secret: U256) -> Self {
+        Self(self.0.mul_secret(secret))
+    }
+}
+
+// 32 バイトのビッグエンディアン。SEC1 や BIP340 の座標・スカラーはこの形で並べる
+pub(crate) fn to_bytes(num: U256) -> [u8; 32] {
+    let mut ret = [0u8; 32];
+    num.to_big_endian(&mut ret);
+    ret
+}
+
+impl fmt::Display for S256Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            Point::Coordinate { x, y, .. } => {
+                write!(f, "S256Point({:064x}, {:064x})", x.num, y.num)
+            }
+            Point::Infinity => write!(f, "S256Point(infinity)"),
+        }
+    }
+}
+
+impl Add for S256Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl Neg for S256Point {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+// G には倍数表を使う。それ以外の点はスカラーの簡約も Point の乗算に任せる
+impl Mul<U256> for S256Point {
+    type Output = Self;
+
+    fn mul(self, other: U256) -> Self {
+        if self == G {
+            return Self::mul_base(other % N);
+        }
+        Self(self.0 * other)
+    }
+}
+
+#[cfg(test)]
+mod secp256k1_tests {
+    use super::*;
+
+    #[test]
+    fn constants() {
+        assert_eq!(
+            P,
+            U256::from_str_radix(
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+                16,
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            N,
+            U256::from_str_radix(
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+                16,
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            GX,
+            U256::from_str_radix(
+                "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+                16,
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            GY,
+            U256::from_str_radix(
+                "483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+                16,
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(S256Point::new(GX, GY), G);
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(S256Point::try_new(GX, GY), Ok(G));
+        assert_eq!(S256Point::try_new(GX, GX), Err(CryptoError::NotOnCurve));
+        assert_eq!(S256Point::try_new(P, GY), Err(CryptoError::OutOfRange));
+    }
+
+    #[test]
+    fn sec() {
+        let cases = [
+            (
+                U256::from(5000),
+                false,
+                "04ffe558e388852f0120e46af2d1b370f85854a8eb0841811ece0e3e03d282d57c315dc72890a4f10a1481c031b03b351b0dc79901ca18a00cf009dbdb157a1d10",
+            ),
+            (
+                U256::from(2018).pow(U256::from(5)),
+                false,
+                "04027f3da1918455e03c46f659266a1bb5204e959db7364d2f473bdf8f0a13cc9dff87647fd023c13b4a4994f17691895806e1b40b57f4fd22581a4f46851f3b06",
+            ),
+            (
+                U256::from(0xdeadbeef12345u64),
+                false,
+                "04d90cd625ee87dd38656dd95cf79f65f60f7273b67d3096e68bd81e4f5342691f842efa762fd59961d0e99803c61edba8b3e3f7dc3a341836f97733aebf987121",
+            ),
+            (
+                U256::from(5001),
+                true,
+                "0357a4f368868a8a6d572991e484e664810ff14c05c0fa023275251151fe0e53d1",
+            ),
+            (
+                U256::from(2019).pow(U256::from(5)),
+                true,
+                "02933ec2d2b111b92737ec12f1c5d20f3233a0ad21cd8b36d0bca7a0cfa5cb8701",
+            ),
+            (
+                U256::from(0xdeadbeef54321u64),
+                true,
+                "0296be5b1292f6c856b3c5654e886fc13511462059089cdf9c479623bfcbe77690",
+            ),
+        ];
+
+        for (secret, compressed, expected) in cases {
+            let point = G * secret;
+            let sec = hex::decode(expected).unwrap();
+            assert_eq!(point.sec(compressed), sec);
+            assert_eq!(S256Point::parse_sec(&sec), Ok(point));
+        }
+        assert_eq!(S256Point::infinity().sec(true), vec![0]);
+        assert_eq!(
+            S256Point::parse_sec(&[0]),
+            Err(CryptoError::InfinityPublicKey)
+        );
+    }
+
+    #[test]
+    fn parse_sec_invalid() {
+        let sec = G.sec(true);
+        assert_eq!(
+            S256Point::parse_sec(&sec[..32]),
+            Err(CryptoError::InvalidEncoding)
+        );
+        assert_eq!(S256Point::parse_sec(&[]), Err(CryptoError::InvalidEncoding));
+
+        let mut wrong_prefix = G.sec(false);
+        wrong_prefix[0] = 5;
+        assert_eq!(
+            S256Point::parse_sec(&wrong_prefix),
+            Err(CryptoError::InvalidEncoding)
+        );
+
+        let mut off_curve = G.sec(false);
+        off_curve[64] ^= 1;
+        assert_eq!(
+            S256Point::parse_sec(&off_curve),
+            Err(CryptoError::NotOnCurve)
+        );
+
+        // x = 5 のとき x^3 + 7 は平方剰余ではない
+        let mut no_root = vec![2];
+        no_root.extend_from_slice(&to_bytes(U256::from(5)));
+        assert_eq!(S256Point::parse_sec(&no_root), Err(CryptoError::NotOnCurve));
+
+        let mut too_large = vec![2];
+        too_large.extend_from_slice(&to_bytes(P));
+        assert_eq!(
+            S256Point::parse_sec(&too_large),
+            Err(CryptoError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn address() {
+        let cases = [
+            (
+                U256::from(5002),
+                false,
+                true,
+                "mmTPbXQFxboEtNRkwfh6K51jvdtHLxGeMA",
+            ),
+            (
+                U256::from(2020).pow(U256::from(5)),
+                true,
+                true,
+                "mopVkxp8UhXqRYbCYJsbeE1h1fiF64jcoH",
+            ),
+            (
+                U256::from(0x12345deadbeefu64),
+                true,
+                false,
+                "1F1Pn2y6pDb68E5nYJJeba4TLg2U7B6KF1",
+            ),
+        ];
+
+        for (secret, compressed, testnet, expected) in cases {
+            assert_eq!((G * secret).address(compressed, testnet), expected);
+        }
+    }
+
+    #[test]
+    fn mul_secret() {
+        let k = U256::from(1234567890);
+        assert_eq!(G.mul_secret(k), G * k);
+        assert_eq!(G.mul_secret(U256::from(2)), G * U256::from(2));
+        assert_eq!(G.mul_secret(U256::one()), G);
+        assert_eq!(G.mul_secret(N - 1), G * (N - 1));
+
+        let k = U256::from_str_radix(
+            "8b387de39861728c92ec9f589c303b1038ff60eb3963b12cd212263a1d1e0f00",
+            16,
+        )
+        .unwrap();
+        assert_eq!(G.mul_secret(k), G * k);
+    }
+
+    #[test]
+    fn multi_mul() {
+        let p = G * U256::from(0xdeadbeefu64);
+        let u = U256::from_str_radix(
+            "8b387de39861728c92ec9f589c303b1038ff60eb3963b12cd212263a1d1e0f00",
+            16,
+        )
+        .unwrap();
+        let v = N - U256::from(12345);
+        assert_eq!(S256Point::multi_mul(&[(u, G), (v, p)]), G * u + p * v);
+        assert_eq!(
+            S256Point::multi_mul(&[(U256::one(), G), (N - 1, G)]),
+            S256Point::infinity()
+        );
+    }
+
+    #[test]
+    fn mul_base() {
+        let p = G * U256::from(3);
+        for k in [
+            U256::zero(),
+            U256::one(),
+            U256::from(0xdeadbeefu64),
+            U256::MAX >> 1,
+            N - 1,
+        ] {
+            assert_eq!(S256Point::mul_base(k), G.mul_secret(k));
+            assert_eq!(S256Point::mul_base(k), S256Point(G.0 * k));
+        }
+        assert_eq!(S256Point::mul_base(N), S256Point::infinity());
+        // G 以外の点には表を使わない
+        assert_eq!(p * U256::from(5), G * U256::from(15));
+    }
+
+    #[test]
+    fn order() {
+        assert_eq!(G * N, S256Point::infinity());
+        assert_eq!(G * (N + U256::one()), G);
+    }
+}
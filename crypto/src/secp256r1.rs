@@ -0,0 +1,232 @@
+use crate::curve::{Curve, Point};
+use crate::field::{Fp, Modular, Reduction};
+use primitive_types::{U256, U512};
+use std::marker::PhantomData;
+
+// NIST P-256 (secp256r1): y^2 = x^3 - 3x + b over F_p, p = 2^256 - 2^224 + 2^192 + 2^96 - 1
+pub const P: U256 = U256([
+    0xFFFFFFFFFFFFFFFF,
+    0x00000000FFFFFFFF,
+    0x0000000000000000,
+    0xFFFFFFFF00000001,
+]);
+
+// Order of G
+pub const N: U256 = U256([
+    0xF3B9CAC2FC632551,
+    0xBCE6FAADA7179E84,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFF00000000,
+]);
+
+pub const GX: U256 = U256([
+    0xF4A13945D898C296,
+    0x77037D812DEB33A0,
+    0xF8BCE6E563A440F2,
+    0x6B17D1F2E12C4247,
+]);
+
+pub const GY: U256 = U256([
+    0xCBB6406837BF51F5,
+    0x2BCE33576B315ECE,
+    0x8EE7EB4A7C0F9E16,
+    0x4FE342E2FE1A7F9B,
+]);
+
+// p は Solinas 素数なので、積を下の reduce で語単位の足し引きだけで簡約する
+#[derive(Clone, Copy, Debug)]
+pub struct P256Reduction;
+
+impl Reduction<U256> for P256Reduction {
+    fn mul_mod(a: U256, b: U256, modulus: U256) -> U256 {
+        debug_assert_eq!(modulus, P);
+        reduce(a.full_mul(b))
+    }
+}
+
+pub type P256Field = Fp<U256, P256Reduction>;
+
+// a = -3 = p - 3
+pub const A: P256Field = P256Field {
+    num: U256([
+        0xFFFFFFFFFFFFFFFC,
+        0x00000000FFFFFFFF,
+        0x0000000000000000,
+        0xFFFFFFFF00000001,
+    ]),
+    prime: P,
+    reduction: PhantomData,
+};
+
+pub const B: P256Field = P256Field {
+    num: U256([
+        0x3BCE3C3E27D2604B,
+        0x651D06B0CC53B0F6,
+        0xB3EBBD55769886BC,
+        0x5AC635D8AA3A93E7,
+    ]),
+    prime: P,
+    reduction: PhantomData,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Secp256r1;
+
+impl Curve for Secp256r1 {
+    type Field = P256Field;
+    type Int = U256;
+
+    const A: P256Field = A;
+    const B: P256Field = B;
+    const ONE: P256Field = P256Field {
+        num: U256([1, 0, 0, 0]),
+        prime: P,
+        reduction: PhantomData,
+    };
+    const P: U256 = P;
+    const N: U256 = N;
+    const H: U256 = U256([1, 0, 0, 0]);
+    const G: Point<Self> = Point::Coordinate {
+        x: P256Field {
+            num: GX,
+            prime: P,
+            reduction: PhantomData,
+        },
+        y: P256Field {
+            num: GY,
+            prime: P,
+            reduction: PhantomData,
+        },
+    };
+}
+
+// p は Solinas 素数なので、積 c = (c15, ..., c0) (32 ビット語) を FIPS 186-4 D.2.3 の
+// s1 + 2s2 + 2s3 + s4 + s5 - d1 - d2 - d3 - d4 に畳み込めば割り算なしで mod p が取れる。
+// 語ごとに符号付きで足し合わせ、2^256 からあふれた分は 2^256 = 2^224 - 2^192 - 2^96 + 1 (mod p)
+// として下の語に戻す
+fn reduce(product: U512) -> U256 {
+    let mut c = [0i64; 16];
+    for (i, limb) in product.0.into_iter().enumerate() {
+        c[2 * i] = (limb & 0xFFFFFFFF) as i64;
+        c[2 * i + 1] = (limb >> 32) as i64;
+    }
+
+    // s1..s5, d1..d4 を語ごとにまとめたもの
+    let mut acc = [
+        c[0] + c[8] + c[9] - c[11] - c[12] - c[13] - c[14],
+        c[1] + c[9] + c[10] - c[12] - c[13] - c[14] - c[15],
+        c[2] + c[10] + c[11] - c[13] - c[14] - c[15],
+        c[3] - c[8] - c[9] + 2 * c[11] + 2 * c[12] + c[13] - c[15],
+        c[4] - c[9] - c[10] + 2 * c[12] + 2 * c[13] + c[14],
+        c[5] - c[10] - c[11] + 2 * c[13] + 2 * c[14] + c[15],
+        c[6] - c[8] - c[9] + c[13] + 3 * c[14] + 2 * c[15],
+        c[7] + c[8] - c[10] - c[11] - c[12] - c[13] + 3 * c[15],
+    ];
+    // あふれは 1 回目が -4..=6、2 回目が -1..=1 で、2 回戻せば 0 になる
+    let mut carry = propagate(&mut acc);
+    for _ in 0..2 {
+        acc[0] += carry;
+        acc[3] -= carry;
+        acc[6] -= carry;
+        acc[7] += carry;
+        carry = propagate(&mut acc);
+    }
+
+    let low = U256(std::array::from_fn(|i| {
+        (acc[2 * i] as u64) | (acc[2 * i + 1] as u64) << 32
+    }));
+    // 2^256 < 2p なので 1 回引けば p 未満になる
+    low.add_mod(U256::zero(), P)
+}
+
+// 各語を 32 ビットに収め、最上位の語からのあふれを返す
+fn propagate(acc: &mut [i64; 8]) -> i64 {
+    let mut carry = 0;
+    for a in acc.iter_mut() {
+        *a += carry;
+        carry = *a >> 32;
+        *a &= 0xFFFFFFFF;
+    }
+    carry
+}
+
+#[cfg(test)]
+mod secp256r1_tests {
+    use super::*;
+    use crate::field::assert_reduction;
+    use proptest::prelude::*;
+
+    #[test]
+    fn constants() {
+        assert_eq!(
+            P,
+            U256::from_str_radix(
+                "FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF",
+                16,
+            )
+            .unwrap()
+        );
+        assert_eq!(A.num, P - 3);
+        assert_eq!(
+            B.num,
+            U256::from_str_radix(
+                "5AC635D8AA3A93E7B3EBBD55769886BC651D06B0CC53B0F63BCE3C3E27D2604B",
+                16,
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            N,
+            U256::from_str_radix(
+                "FFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551",
+                16,
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn generator() {
+        let g = Point::<Secp256r1>::try_new(P256Field::new(GX, P), P256Field::new(GY, P));
+        assert_eq!(g, Ok(Secp256r1::G));
+        assert_eq!(Secp256r1::G * N, Point::Infinity);
+        assert_eq!(Secp256r1::G * (N + 1), Secp256r1::G);
+
+        let x = U256::from_str_radix(
+            "7CF27B188D034F7E8A52380304B51AC3C08969E277F21B35A60B48FC47669978",
+            16,
+        )
+        .unwrap();
+        let y = U256::from_str_radix(
+            "07775510DB8ED040293D9AC69F7430DBBA7DADE63CE982299E04B79D227873D1",
+            16,
+        )
+        .unwrap();
+        assert_eq!(
+            Secp256r1::G + Secp256r1::G,
+            Point::new(P256Field::new(x, P), P256Field::new(y, P))
+        );
+    }
+
+    #[test]
+    fn solinas_reduction() {
+        let values = [
+            U256::zero(),
+            U256::one(),
+            P - 1,
+            U256::MAX,
+            GX,
+            GY,
+            B.num,
+            U256::from(0xFFFFFFFFu64) << 224,
+        ];
+        assert_reduction::<_, P256Reduction>(&values, P);
+    }
+
+    proptest! {
+        #[test]
+        fn solinas_reduction_random(a in any::<[u64; 4]>(), b in any::<[u64; 4]>()) {
+            assert_reduction::<_, P256Reduction>(&[U256(a), U256(b)], P);
+        }
+    }
+}
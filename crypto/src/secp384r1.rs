@@ -0,0 +1,156 @@
+use crate::curve::{Curve, Point};
+use crate::field::FieldElement;
+use primitive_types::U512;
+use std::marker::PhantomData;
+
+// NIST P-384 (secp384r1): y^2 = x^3 - 3x + b over F_p, p = 2^384 - 2^128 - 2^96 + 2^32 - 1。
+// 384 ビットの整数型はないので U512 の下位 6 語を使う
+pub const P: U512 = U512([
+    0x00000000FFFFFFFF,
+    0xFFFFFFFF00000000,
+    0xFFFFFFFFFFFFFFFE,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0,
+    0,
+]);
+
+// Order of G
+pub const N: U512 = U512([
+    0xECEC196ACCC52973,
+    0x581A0DB248B0A77A,
+    0xC7634D81F4372DDF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0,
+    0,
+]);
+
+pub const GX: U512 = U512([
+    0x3A545E3872760AB7,
+    0x5502F25DBF55296C,
+    0x59F741E082542A38,
+    0x6E1D3B628BA79B98,
+    0x8EB1C71EF320AD74,
+    0xAA87CA22BE8B0537,
+    0,
+    0,
+]);
+
+pub const GY: U512 = U512([
+    0x7A431D7C90EA0E5F,
+    0x0A60B1CE1D7E819D,
+    0xE9DA3113B5F0B8C0,
+    0xF8F41DBD289A147C,
+    0x5D9E98BF9292DC29,
+    0x3617DE4A96262C6F,
+    0,
+    0,
+]);
+
+pub type P384Field = FieldElement<U512>;
+
+// a = -3 = p - 3
+pub const A: P384Field = FieldElement {
+    num: U512([
+        0x00000000FFFFFFFC,
+        0xFFFFFFFF00000000,
+        0xFFFFFFFFFFFFFFFE,
+        0xFFFFFFFFFFFFFFFF,
+        0xFFFFFFFFFFFFFFFF,
+        0xFFFFFFFFFFFFFFFF,
+        0,
+        0,
+    ]),
+    prime: P,
+    reduction: PhantomData,
+};
+
+pub const B: P384Field = FieldElement {
+    num: U512([
+        0x2A85C8EDD3EC2AEF,
+        0xC656398D8A2ED19D,
+        0x0314088F5013875A,
+        0x181D9C6EFE814112,
+        0x988E056BE3F82D19,
+        0xB3312FA7E23EE7E4,
+        0,
+        0,
+    ]),
+    prime: P,
+    reduction: PhantomData,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Secp384r1;
+
+impl Curve for Secp384r1 {
+    type Field = P384Field;
+    type Int = U512;
+
+    const A: P384Field = A;
+    const B: P384Field = B;
+    const ONE: P384Field = FieldElement {
+        num: U512([1, 0, 0, 0, 0, 0, 0, 0]),
+        prime: P,
+        reduction: PhantomData,
+    };
+    const P: U512 = P;
+    const N: U512 = N;
+    const H: U512 = U512([1, 0, 0, 0, 0, 0, 0, 0]);
+    const G: Point<Self> = Point::Coordinate {
+        x: FieldElement {
+            num: GX,
+            prime: P,
+            reduction: PhantomData,
+        },
+        y: FieldElement {
+            num: GY,
+            prime: P,
+            reduction: PhantomData,
+        },
+    };
+}
+
+#[cfg(test)]
+mod secp384r1_tests {
+    use super::*;
+
+    fn hex(s: &str) -> U512 {
+        U512::from_str_radix(s, 16).unwrap()
+    }
+
+    #[test]
+    fn constants() {
+        let one = U512::one();
+        assert_eq!(
+            P,
+            (one << 384) - (one << 128) - (one << 96) + (one << 32) - one
+        );
+        assert_eq!(A.num, P - 3);
+        assert_eq!(
+            N,
+            hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFC7634D81F4372DDF581A0DB248B0A77AECEC196ACCC52973")
+        );
+        assert_eq!(
+            B.num,
+            hex("B3312FA7E23EE7E4988E056BE3F82D19181D9C6EFE8141120314088F5013875AC656398D8A2ED19D2A85C8EDD3EC2AEF")
+        );
+    }
+
+    #[test]
+    fn generator() {
+        let g = Point::<Secp384r1>::try_new(FieldElement::new(GX, P), FieldElement::new(GY, P));
+        assert_eq!(g, Ok(Secp384r1::G));
+        assert_eq!(Secp384r1::G * N, Point::Infinity);
+
+        let x = hex("08D999057BA3D2D969260045C55B97F089025959A6F434D651D207D19FB96E9E4FE0E86EBE0E64F85B96A9C75295DF61");
+        let y = hex("8E80F1FA5B1B3CEDB7BFE8DFFD6DBA74B275D875BC6CC43E904E505F256AB4255FFD43E94D39E22D61501E700A940E80");
+        assert_eq!(
+            Secp384r1::G + Secp384r1::G,
+            Point::new(FieldElement::new(x, P), FieldElement::new(y, P))
+        );
+    }
+}
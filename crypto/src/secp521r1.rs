@@ -0,0 +1,206 @@
+use crate::curve::{Curve, Point};
+use crate::field::{Fp, Modular, Reduction, U576};
+use std::marker::PhantomData;
+
+// NIST P-521 (secp521r1): y^2 = x^3 - 3x + b over F_p, p = 2^521 - 1。
+// 521 ビットは U512 に入らないので U576 を使う
+pub const P: U576 = U576([
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0x00000000000001FF,
+]);
+
+// Order of G
+pub const N: U576 = U576([
+    0xBB6FB71E91386409,
+    0x3BB5C9B8899C47AE,
+    0x7FCC0148F709A5D0,
+    0x51868783BF2F966B,
+    0xFFFFFFFFFFFFFFFA,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0x00000000000001FF,
+]);
+
+pub const GX: U576 = U576([
+    0xF97E7E31C2E5BD66,
+    0x3348B3C1856A429B,
+    0xFE1DC127A2FFA8DE,
+    0xA14B5E77EFE75928,
+    0xF828AF606B4D3DBA,
+    0x9C648139053FB521,
+    0x9E3ECB662395B442,
+    0x858E06B70404E9CD,
+    0x00000000000000C6,
+]);
+
+pub const GY: U576 = U576([
+    0x88BE94769FD16650,
+    0x353C7086A272C240,
+    0xC550B9013FAD0761,
+    0x97EE72995EF42640,
+    0x17AFBD17273E662C,
+    0x98F54449579B4468,
+    0x5C8A5FB42C7D1BD9,
+    0x39296A789A3BC004,
+    0x0000000000000118,
+]);
+
+// p = 2^521 - 1 はメルセンヌ素数なので、積の上半分を下に足すだけで簡約できる (下の mul)
+#[derive(Clone, Copy, Debug)]
+pub struct P521Reduction;
+
+impl Reduction<U576> for P521Reduction {
+    fn mul_mod(a: U576, b: U576, modulus: U576) -> U576 {
+        debug_assert_eq!(modulus, P);
+        mul(a, b)
+    }
+}
+
+pub type P521Field = Fp<U576, P521Reduction>;
+
+// a = -3 = p - 3
+pub const A: P521Field = P521Field {
+    num: U576([
+        0xFFFFFFFFFFFFFFFC,
+        0xFFFFFFFFFFFFFFFF,
+        0xFFFFFFFFFFFFFFFF,
+        0xFFFFFFFFFFFFFFFF,
+        0xFFFFFFFFFFFFFFFF,
+        0xFFFFFFFFFFFFFFFF,
+        0xFFFFFFFFFFFFFFFF,
+        0xFFFFFFFFFFFFFFFF,
+        0x00000000000001FF,
+    ]),
+    prime: P,
+    reduction: PhantomData,
+};
+
+pub const B: P521Field = P521Field {
+    num: U576([
+        0xEF451FD46B503F00,
+        0x3573DF883D2C34F1,
+        0x1652C0BD3BB1BF07,
+        0x56193951EC7E937B,
+        0xB8B489918EF109E1,
+        0xA2DA725B99B315F3,
+        0x929A21A0B68540EE,
+        0x953EB9618E1C9A1F,
+        0x0000000000000051,
+    ]),
+    prime: P,
+    reduction: PhantomData,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Secp521r1;
+
+impl Curve for Secp521r1 {
+    type Field = P521Field;
+    type Int = U576;
+
+    const A: P521Field = A;
+    const B: P521Field = B;
+    const ONE: P521Field = P521Field {
+        num: U576([1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        prime: P,
+        reduction: PhantomData,
+    };
+    const P: U576 = P;
+    const N: U576 = N;
+    const H: U576 = U576([1, 0, 0, 0, 0, 0, 0, 0, 0]);
+    const G: Point<Self> = Point::Coordinate {
+        x: P521Field {
+            num: GX,
+            prime: P,
+            reduction: PhantomData,
+        },
+        y: P521Field {
+            num: GY,
+            prime: P,
+            reduction: PhantomData,
+        },
+    };
+}
+
+// a * b mod p (a, b < p)。積 (1042 ビット未満) を下位 521 ビットと残りに分け、
+// 2^521 = 1 (mod p) なので両者を足すだけで簡約できる
+fn mul(a: U576, b: U576) -> U576 {
+    let mut product = [0u64; 18];
+    for (i, ai) in a.0.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, bj) in b.0.iter().enumerate() {
+            let wide = product[i + j] as u128 + *ai as u128 * *bj as u128 + carry;
+            product[i + j] = wide as u64;
+            carry = wide >> 64;
+        }
+        product[i + 9] = carry as u64;
+    }
+
+    let low = U576(std::array::from_fn(|i| product[i])) & P;
+    let high = U576(std::array::from_fn(|i| {
+        (product[i + 8] >> 9) | (product[i + 9] << 55)
+    }));
+    // low <= p, high < p なので 1 回引けば p 未満になる
+    low.add_mod(high, P)
+}
+
+#[cfg(test)]
+mod secp521r1_tests {
+    use super::*;
+    use crate::field::assert_reduction;
+    use proptest::prelude::*;
+
+    fn hex(s: &str) -> U576 {
+        U576::from_str_radix(s, 16).unwrap()
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(P, (U576::one() << 521) - 1);
+        assert_eq!(A.num, P - 3);
+        assert_eq!(
+            N,
+            hex("1FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFA51868783BF2F966B7FCC0148F709A5D03BB5C9B8899C47AEBB6FB71E91386409")
+        );
+        assert_eq!(
+            B.num,
+            hex("51953EB9618E1C9A1F929A21A0B68540EEA2DA725B99B315F3B8B489918EF109E156193951EC7E937B1652C0BD3BB1BF073573DF883D2C34F1EF451FD46B503F00")
+        );
+    }
+
+    #[test]
+    fn generator() {
+        let g = Point::<Secp521r1>::try_new(P521Field::new(GX, P), P521Field::new(GY, P));
+        assert_eq!(g, Ok(Secp521r1::G));
+        assert_eq!(Secp521r1::G * N, Point::Infinity);
+
+        let x = hex("433C219024277E7E682FCB288148C282747403279B1CCC06352C6E5505D769BE97B3B204DA6EF55507AA104A3A35C5AF41CF2FA364D60FD967F43E3933BA6D783D");
+        let y = hex("F4BB8CC7F86DB26700A7F3ECEEEED3F0B5C6B5107C4DA97740AB21A29906C42DBBB3E377DE9F251F6B93937FA99A3248F4EAFCBE95EDC0F4F71BE356D661F41B02");
+        assert_eq!(
+            Secp521r1::G + Secp521r1::G,
+            Point::new(P521Field::new(x, P), P521Field::new(y, P))
+        );
+    }
+
+    #[test]
+    fn mersenne_reduction() {
+        let values = [U576::zero(), U576::one(), P - 1, GX, GY, B.num, P >> 1];
+        assert_reduction::<_, P521Reduction>(&values, P);
+        assert_eq!(mul(P - 1, P - 1), U576::one());
+    }
+
+    proptest! {
+        #[test]
+        fn mersenne_reduction_random(a in any::<[u64; 9]>(), b in any::<[u64; 9]>()) {
+            assert_reduction::<_, P521Reduction>(&[U576(a), U576(b)], P);
+        }
+    }
+}